@@ -1,22 +1,137 @@
+use std::collections::HashSet;
 use std::io::{Write, stdout};
 use std::process::exit;
 use std::time::Duration;
 
-use capnp_rpc::{RpcSystem, rpc_twoparty_capnp, twoparty};
+use capnp::capability::Promise;
+use capnp_rpc::{RpcSystem, pry, rpc_twoparty_capnp, twoparty};
 use futures::AsyncReadExt;
 use termion::event::{Event, Key};
 use termion::input::TermRead;
 use termion::raw::IntoRawMode;
 use tokio::time::sleep;
 
-pub mod schema_capnp {
-    include!("rpc/schema_capnp.rs");
-}
-
+use proto::schema_capnp;
 use schema_capnp::bootstrap;
 use schema_capnp::hello_service;
+use schema_capnp::rotate_absolute_feedback;
+use schema_capnp::status_listener;
+use schema_capnp::turtlesim_service;
 use schema_capnp::twist_service;
 
+// Routers from before getInfo offered these unconditionally
+const LEGACY_SERVICES: [&str; 3] = ["hello", "twist", "turtlesim"];
+
+// Keys, the service each one needs and what it does
+const CONTROLS: [(&str, &str, &str); 8] = [
+    ("Right arrow", "twist", "Send twist message"),
+    ("Left arrow", "hello", "Send hello message"),
+    ("Up arrow", "twist", "Send forward twist"),
+    ("s", "turtlesim", "Spawn a turtle and drive it"),
+    ("c", "turtlesim", "Clear the turtlesim canvas"),
+    ("r", "turtlesim", "Rotate the driven turtle to face east"),
+    ("b", "parameters", "Cycle the turtlesim background color"),
+    (
+        "g",
+        "publisher",
+        "Spin the driven turtle through a generic JSON publisher",
+    ),
+];
+
+// Asks the router what it offers, assuming the legacy services when it predates getInfo
+async fn negotiate(bootstrap: &bootstrap::Client) -> Result<HashSet<String>, capnp::Error> {
+    let response = match bootstrap.get_info_request().send().promise.await {
+        Ok(response) => response,
+        Err(e) if e.kind == capnp::ErrorKind::Unimplemented => {
+            println!("✓ Router predates getInfo, assuming hello, twist and turtlesim");
+            return Ok(LEGACY_SERVICES.iter().map(|s| s.to_string()).collect());
+        }
+        Err(e) => return Err(e),
+    };
+    let info = response.get()?.get_info()?;
+    let schema_version = info.get_schema_version();
+    println!(
+        "✓ Router {} (schema version {})",
+        info.get_router_version()?.to_str()?,
+        schema_version
+    );
+    if schema_version < schema_capnp::SCHEMA_VERSION {
+        println!(
+            "  Router schema is older than ours ({}), newer features are unavailable",
+            schema_capnp::SCHEMA_VERSION
+        );
+    } else if schema_version > schema_capnp::SCHEMA_VERSION {
+        println!(
+            "  Router schema is newer than ours ({}), consider updating the client",
+            schema_capnp::SCHEMA_VERSION
+        );
+    }
+    let features = info
+        .get_features()?
+        .iter()
+        .map(|feature| Ok(feature?.to_string()?))
+        .collect::<Result<Vec<_>, capnp::Error>>()?;
+    if !features.is_empty() {
+        println!("  Features: {}", features.join(", "));
+    }
+    info.get_services()?
+        .iter()
+        .map(|service| Ok(service?.to_string()?))
+        .collect()
+}
+
+// Prints the feedback of a rotate_absolute goal as it arrives
+struct RotateFeedbackPrinter;
+
+impl rotate_absolute_feedback::Server for RotateFeedbackPrinter {
+    fn on_feedback(
+        &mut self,
+        params: rotate_absolute_feedback::OnFeedbackParams,
+        _: rotate_absolute_feedback::OnFeedbackResults,
+    ) -> Promise<(), capnp::Error> {
+        let remaining = pry!(params.get()).get_remaining();
+        print!("\r\n  remaining: {:.3} rad", remaining);
+        stdout().flush().unwrap();
+        Promise::ok(())
+    }
+}
+
+// Warns when what the router publishes stops reaching anyone, in raw mode lines start with \r
+struct StatusPrinter {
+    last: Option<schema_capnp::LinkState>,
+}
+
+impl status_listener::Server for StatusPrinter {
+    fn on_status(
+        &mut self,
+        params: status_listener::OnStatusParams,
+        _: status_listener::OnStatusResults,
+    ) -> Promise<(), capnp::Error> {
+        let status = pry!(pry!(params.get()).get_status());
+        let state = pry!(status.get_state());
+        if self.last.replace(state) == Some(state) {
+            return Promise::ok(());
+        }
+        match state {
+            schema_capnp::LinkState::Connected => println!("\r\n✓ Router linked to the robots"),
+            schema_capnp::LinkState::Disconnected => {
+                println!("\r\n⚠ Router is not linked to any Zenoh node, commands go nowhere")
+            }
+            schema_capnp::LinkState::NoSubscribers => {
+                for topic in pry!(status.get_topics()) {
+                    if pry!(topic.get_state()) == schema_capnp::LinkState::NoSubscribers {
+                        println!(
+                            "\r\n⚠ Nothing subscribes to {}",
+                            pry!(pry!(topic.get_topic()).to_str())
+                        );
+                    }
+                }
+            }
+        }
+        Promise::ok(())
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = std::env::var("RPC_SERVER_ADDR").unwrap_or_else(|_| {
@@ -24,13 +139,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         exit(1);
     });
 
-    // Print initial instructions to normal stdout
     println!("Cap'n Proto Bootstrap Client");
-    println!("Controls:");
-    println!("  Right arrow: Send twist message");
-    println!("  Left arrow:  Send hello message");
-    println!("  Up arrow:    Send forward twist");
-    println!("  q:           Exit");
     println!("Connecting to {}...", addr);
 
     let stdin = std::io::stdin();
@@ -59,34 +168,79 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             tokio::task::spawn_local(rpc_system);
             println!("✓ RPC System started");
 
+            let services = negotiate(&bootstrap_client).await?;
+
             // Get the hello service from bootstrap
-            print!("Getting hello service from bootstrap... ");
-            stdout().flush().unwrap();
-            let hello_request = bootstrap_client.get_hello_service_request();
-            let hello_response = hello_request.send().promise.await?;
-            let hello: hello_service::Client = hello_response.get()?.get_service()?;
-            println!("✓ Hello service ready");
+            let mut hello: Option<hello_service::Client> = None;
+            if services.contains("hello") {
+                print!("Getting hello service from bootstrap... ");
+                stdout().flush().unwrap();
+                let hello_request = bootstrap_client.get_hello_service_request();
+                let hello_response = hello_request.send().promise.await?;
+                hello = Some(hello_response.get()?.get_service()?);
+                println!("✓ Hello service ready");
+            }
 
             // Get the twist service from bootstrap
-            print!("Getting twist service from bootstrap... ");
-            stdout().flush().unwrap();
-            let twist_request = bootstrap_client.get_twist_service_request();
-            let twist_response = twist_request.send().promise.await?;
-            let twist: twist_service::Client = twist_response.get()?.get_service()?;
-            println!("✓ Twist service ready");
+            let mut twist: Option<twist_service::Client> = None;
+            if services.contains("twist") {
+                print!("Getting twist service from bootstrap... ");
+                stdout().flush().unwrap();
+                let twist_request = bootstrap_client.get_twist_service_request();
+                let twist_response = twist_request.send().promise.await?;
+                twist = Some(twist_response.get()?.get_service()?);
+                println!("✓ Twist service ready");
+            }
+            let mut turtle_name = String::from("turtle1");
+
+            // Backgrounds cycled with 'b', applied through turtlesim's parameters
+            let backgrounds: [(i64, i64, i64); 3] = [(69, 86, 255), (30, 30, 30), (40, 120, 60)];
+            let mut background = 0;
+
+            // Get the turtlesim service from bootstrap
+            let mut turtlesim: Option<turtlesim_service::Client> = None;
+            if services.contains("turtlesim") {
+                print!("Getting turtlesim service from bootstrap... ");
+                stdout().flush().unwrap();
+                let turtlesim_request = bootstrap_client.get_turtlesim_service_request();
+                let turtlesim_response = turtlesim_request.send().promise.await?;
+                turtlesim = Some(turtlesim_response.get()?.get_service()?);
+                println!("✓ Turtlesim service ready");
+            }
+
+            // Kept for the session, status is pushed until it is dropped
+            let _status = if services.contains("status") {
+                let mut request = bootstrap_client.watch_status_request();
+                request
+                    .get()
+                    .set_listener(capnp_rpc::new_client(StatusPrinter { last: None }));
+                Some(request.send().promise.await?.get()?.get_subscription()?)
+            } else {
+                None
+            };
 
             // Send initial hello message
-            print!("Sending initial hello message... ");
-            stdout().flush().unwrap();
-            let mut hello_request = hello.do_hello_request();
-            hello_request
-                .get()
-                .init_data()
-                .set_msg("hello from bootstrap client".to_string());
-            hello_request.send().promise.await?;
-            println!("✓ Initial hello sent");
-
-            println!("\nReady! Use arrow keys to send messages, 'q' to quit.");
+            if let Some(hello) = &hello {
+                print!("Sending initial hello message... ");
+                stdout().flush().unwrap();
+                let mut hello_request = hello.do_hello_request();
+                hello_request
+                    .get()
+                    .init_data()
+                    .set_msg("hello from bootstrap client".to_string());
+                hello_request.send().promise.await?;
+                println!("✓ Initial hello sent");
+            }
+
+            // Only offer the controls whose service the router has
+            println!("Controls:");
+            for (key, service, description) in CONTROLS {
+                if services.contains(service) {
+                    println!("  {:<12} {}", format!("{}:", key), description);
+                }
+            }
+            println!("  {:<12} Exit", "q:");
+            println!("\nReady! 'q' to quit.");
 
             // Put stdin in raw mode only for event handling
             let _stdout = std::io::stdout().into_raw_mode().unwrap();
@@ -100,6 +254,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         break;
                     }
                     Event::Key(Key::Right) => {
+                        let Some(twist) = &twist else { continue };
                         print!("\r\nSending twist message (right)... ");
                         stdout().flush().unwrap();
                         let mut request_twist = twist.do_twist_request();
@@ -116,7 +271,119 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         println!("✓ Twist message sent");
                         sleep(Duration::from_millis(100)).await;
                     }
+                    Event::Key(Key::Char('s')) => {
+                        let Some(turtlesim) = &turtlesim else {
+                            continue;
+                        };
+                        print!("\r\nSpawning turtle... ");
+                        stdout().flush().unwrap();
+                        let mut spawn_request = turtlesim.spawn_request();
+                        let mut params = spawn_request.get();
+                        params.set_x(2.0);
+                        params.set_y(2.0);
+                        params.set_theta(0.0);
+                        params.set_name("");
+                        match spawn_request.send().promise.await {
+                            Ok(response) => {
+                                let response = response.get()?;
+                                twist = Some(response.get_twist()?);
+                                turtle_name = response.get_name()?.to_string()?;
+                                println!(
+                                    "✓ Spawned {}, now driving it",
+                                    response.get_name()?.to_str()?
+                                );
+                            }
+                            Err(e) => println!("✗ Spawn failed: {}", e),
+                        }
+                    }
+                    Event::Key(Key::Char('r')) => {
+                        let Some(turtlesim) = &turtlesim else {
+                            continue;
+                        };
+                        print!("\r\nRotating {}... ", turtle_name);
+                        stdout().flush().unwrap();
+                        let mut rotate_request = turtlesim.rotate_absolute_request();
+                        let mut params = rotate_request.get();
+                        params.set_name(&turtle_name);
+                        params.set_theta(0.0);
+                        params.set_feedback(capnp_rpc::new_client(RotateFeedbackPrinter));
+                        let goal = rotate_request.send().pipeline.get_goal();
+                        match goal.get_result_request().send().promise.await {
+                            Ok(response) => {
+                                let response = response.get()?;
+                                println!(
+                                    "\r\n✓ Rotation finished ({:?}), turned {:.3} rad",
+                                    response.get_status()?,
+                                    response.get_delta()
+                                );
+                            }
+                            Err(e) => println!("\r\n✗ Rotation failed: {}", e),
+                        }
+                    }
+                    Event::Key(Key::Char('b')) if services.contains("parameters") => {
+                        background = (background + 1) % backgrounds.len();
+                        let (r, g, b) = backgrounds[background];
+                        print!("\r\nSetting background to ({}, {}, {})... ", r, g, b);
+                        stdout().flush().unwrap();
+                        let mut set_request = bootstrap_client.set_parameters_request();
+                        let mut params = set_request.get();
+                        params.set_node("turtlesim");
+                        let mut parameters = params.init_parameters(3);
+                        for (i, (name, value)) in [
+                            ("background_r", r),
+                            ("background_g", g),
+                            ("background_b", b),
+                        ]
+                        .into_iter()
+                        .enumerate()
+                        {
+                            let mut parameter = parameters.reborrow().get(i as u32);
+                            parameter.set_name(name);
+                            parameter.init_value().set_integer_value(value);
+                        }
+                        match set_request.send().promise.await {
+                            Ok(response) => {
+                                let results = response.get()?.get_results()?;
+                                match results.iter().find(|result| !result.get_successful()) {
+                                    Some(failed) => {
+                                        println!("✗ Rejected: {}", failed.get_reason()?.to_str()?)
+                                    }
+                                    None => println!("✓ Background set"),
+                                }
+                            }
+                            Err(e) => println!("✗ Setting parameters failed: {}", e),
+                        }
+                    }
+                    Event::Key(Key::Char('g')) if services.contains("publisher") => {
+                        print!("\r\nSpinning {} via generic publisher... ", turtle_name);
+                        stdout().flush().unwrap();
+                        let mut publisher_request = bootstrap_client.get_publisher_request();
+                        let mut params = publisher_request.get();
+                        params.set_topic(format!("{}/cmd_vel", turtle_name));
+                        params.set_ros_type("geometry_msgs/msg/Twist");
+                        let publisher = publisher_request.send().pipeline.get_publisher();
+                        let mut publish_request = publisher.publish_json_request();
+                        publish_request
+                            .get()
+                            .set_json(r#"{"angular": {"x": 0.0, "y": 0.0, "z": 2.0}}"#);
+                        match publish_request.send().promise.await {
+                            Ok(_) => println!("✓ Twist published"),
+                            Err(e) => println!("✗ Publish failed: {}", e),
+                        }
+                    }
+                    Event::Key(Key::Char('c')) => {
+                        let Some(turtlesim) = &turtlesim else {
+                            continue;
+                        };
+                        print!("\r\nClearing canvas... ");
+                        stdout().flush().unwrap();
+                        match turtlesim.clear_request().send().promise.await {
+                            Ok(_) => println!("✓ Canvas cleared"),
+                            Err(e) => println!("✗ Clear failed: {}", e),
+                        }
+                    }
                     Event::Key(Key::Left) => {
+                        let Some(hello) = &hello else { continue };
                         print!("\r\nSending hello message... ");
                         stdout().flush().unwrap();
                         let mut hello_request = hello.do_hello_request();
@@ -129,6 +396,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         sleep(Duration::from_millis(100)).await;
                     }
                     Event::Key(Key::Up) => {
+                        let Some(twist) = &twist else { continue };
                         print!("\r\nSending forward twist message... ");
                         stdout().flush().unwrap();
                         let mut request_twist = twist.do_twist_request();
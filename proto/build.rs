@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+#[path = "src/ros_idl.rs"]
+mod ros_idl;
+
+#[path = "ros_codegen.rs"]
+mod ros_codegen;
+
+fn main() {
+    println!("cargo:rerun-if-changed=schema.capnp");
+    println!("cargo:rerun-if-changed=ros_codegen.rs");
+    println!("cargo:rerun-if-changed=src/ros_idl.rs");
+    println!("cargo:rerun-if-changed=../msgs");
+
+    let registry =
+        ros_idl::TypeRegistry::load_dir("../msgs").expect("Error loading ROS message definitions");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    std::fs::write(
+        out_dir.join("ros.capnp"),
+        ros_codegen::capnp_schema(&registry),
+    )
+    .expect("Error writing generated ROS schema");
+    std::fs::write("src/rpc/ros_types.rs", ros_codegen::rust_types(&registry))
+        .expect("Error writing generated ROS types");
+
+    capnpc::CompilerCommand::new()
+        .src_prefix(&out_dir)
+        .import_path(&out_dir)
+        .file("schema.capnp")
+        .file(out_dir.join("ros.capnp"))
+        .output_path("src/rpc")
+        // Read by the schema compatibility test
+        .raw_code_generator_request_path(out_dir.join("schema.cgr"))
+        .run()
+        .expect("Error compiling capnp schema");
+}
@@ -0,0 +1,524 @@
+// Generates a Cap'n Proto schema and Rust conversion code from the ROS message
+// definitions in msgs/. Used by the build script of this crate, which provides the
+// `ros_idl` module it builds on.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::ros_idl::{BaseType, Container, FieldDef, MessageDef, Primitive, TypeRegistry};
+
+const HEADER: &str = "Generated from the ROS message definitions in msgs/, do not edit";
+
+// File id of the generated ros.capnp
+const FILE_ID: &str = "0xd1a6b3c7e4f25a90";
+
+// Same conversion capnpc-rust applies to names, used to predict its module and
+// accessor names
+fn camel_to_snake(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            result.push('_');
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+// `geometry_msgs` -> `GeometryMsgs`, `linear_velocity` -> `linearVelocity`
+fn snake_to_camel(name: &str, upper_first: bool) -> String {
+    let mut result = String::new();
+    let mut upper = upper_first;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn rust_ident(name: &str) -> String {
+    match name {
+        "type" | "match" | "ref" | "move" | "mod" | "use" | "loop" | "box" | "in" | "fn" => {
+            format!("r#{}", name)
+        }
+        _ => name.to_string(),
+    }
+}
+
+// Splits `pkg/msg/Name` into package and name
+fn split(name: &str) -> (&str, &str) {
+    let mut parts = name.split('/');
+    let package = parts.next().unwrap();
+    (package, parts.next_back().unwrap())
+}
+
+// Only plain messages are generated, services and actions stay on the dynamic path
+fn messages_by_package(registry: &TypeRegistry) -> BTreeMap<&str, Vec<&MessageDef>> {
+    let mut packages: BTreeMap<&str, Vec<&MessageDef>> = BTreeMap::new();
+    for message in registry.messages() {
+        if message.name.split('/').nth(1) == Some("msg") {
+            packages
+                .entry(split(&message.name).0)
+                .or_default()
+                .push(message);
+        }
+    }
+    for messages in packages.values_mut() {
+        messages.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    packages
+}
+
+fn is_bytes(field: &FieldDef) -> bool {
+    field.container != Container::Single
+        && matches!(
+            field.base,
+            BaseType::Primitive(Primitive::Byte | Primitive::UInt8)
+        )
+}
+
+fn capnp_base(base: &BaseType) -> String {
+    match base {
+        BaseType::Primitive(primitive) => match primitive {
+            Primitive::Bool => "Bool",
+            Primitive::Byte | Primitive::Char | Primitive::UInt8 => "UInt8",
+            Primitive::Int8 => "Int8",
+            Primitive::Int16 => "Int16",
+            Primitive::UInt16 => "UInt16",
+            Primitive::Int32 => "Int32",
+            Primitive::UInt32 => "UInt32",
+            Primitive::Int64 => "Int64",
+            Primitive::UInt64 => "UInt64",
+            Primitive::Float32 => "Float32",
+            Primitive::Float64 => "Float64",
+            Primitive::String => "Text",
+        }
+        .to_string(),
+        BaseType::BoundedString(_) => "Text".to_string(),
+        BaseType::Message(name) => {
+            let (package, name) = split(name);
+            format!("{}.{}", snake_to_camel(package, true), name)
+        }
+    }
+}
+
+// Cap'n Proto schema with one struct per ROS package, holding a struct per message
+pub fn capnp_schema(registry: &TypeRegistry) -> String {
+    let mut out = String::new();
+    writeln!(out, "# {}", HEADER).unwrap();
+    writeln!(out, "@{};", FILE_ID).unwrap();
+
+    for (package, messages) in messages_by_package(registry) {
+        writeln!(out, "\nstruct {} {{", snake_to_camel(package, true)).unwrap();
+        for message in messages {
+            writeln!(out, "  struct {} {{", split(&message.name).1).unwrap();
+            for (ordinal, field) in message.fields.iter().enumerate() {
+                let ty = if is_bytes(field) {
+                    "Data".to_string()
+                } else if field.container == Container::Single {
+                    capnp_base(&field.base)
+                } else {
+                    format!("List({})", capnp_base(&field.base))
+                };
+                writeln!(
+                    out,
+                    "    {} @{} :{};",
+                    snake_to_camel(&field.name, false),
+                    ordinal,
+                    ty
+                )
+                .unwrap();
+            }
+            writeln!(out, "  }}").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    out
+}
+
+fn rust_primitive(primitive: Primitive) -> &'static str {
+    match primitive {
+        Primitive::Bool => "bool",
+        Primitive::Byte | Primitive::Char | Primitive::UInt8 => "u8",
+        Primitive::Int8 => "i8",
+        Primitive::Int16 => "i16",
+        Primitive::UInt16 => "u16",
+        Primitive::Int32 => "i32",
+        Primitive::UInt32 => "u32",
+        Primitive::Int64 => "i64",
+        Primitive::UInt64 => "u64",
+        Primitive::Float32 => "f32",
+        Primitive::Float64 => "f64",
+        Primitive::String => "::std::string::String",
+    }
+}
+
+fn rust_base(base: &BaseType) -> String {
+    match base {
+        BaseType::Primitive(primitive) => rust_primitive(*primitive).to_string(),
+        BaseType::BoundedString(_) => rust_primitive(Primitive::String).to_string(),
+        BaseType::Message(name) => {
+            let (package, name) = split(name);
+            format!("super::{}::{}", package, name)
+        }
+    }
+}
+
+fn rust_type(field: &FieldDef) -> String {
+    let base = rust_base(&field.base);
+    match field.container {
+        Container::Single => base,
+        Container::Array(size) => format!("[{}; {}]", base, size),
+        Container::Sequence(_) => format!("::std::vec::Vec<{}>", base),
+    }
+}
+
+fn capnp_module(message: &str) -> String {
+    let (package, name) = split(message);
+    format!(
+        "crate::ros_capnp::{}::{}",
+        camel_to_snake(&snake_to_camel(package, true)),
+        camel_to_snake(name)
+    )
+}
+
+// Rust literal for a .msg default or constant value
+fn rust_literal(primitive: Primitive, text: &str) -> String {
+    let text = text.trim();
+    match primitive {
+        Primitive::Bool => match text {
+            "true" | "True" | "1" => "true".to_string(),
+            _ => "false".to_string(),
+        },
+        Primitive::String => {
+            let unquoted = text
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .or_else(|| text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')))
+                .unwrap_or(text);
+            format!("{:?}", unquoted)
+        }
+        Primitive::Float32 | Primitive::Float64
+            if !text.contains(['.', 'e', 'E']) && !text.contains("inf") =>
+        {
+            format!("{}.0", text)
+        }
+        _ => text.to_string(),
+    }
+}
+
+fn rust_default(field: &FieldDef) -> String {
+    let primitive = match &field.base {
+        BaseType::Primitive(primitive) => Some(*primitive),
+        BaseType::BoundedString(_) => Some(Primitive::String),
+        BaseType::Message(_) => None,
+    };
+    match (&field.default, primitive) {
+        (Some(default), Some(primitive)) => {
+            let to_owned = if primitive == Primitive::String {
+                ".to_string()"
+            } else {
+                ""
+            };
+            match field.container {
+                Container::Single => format!("{}{}", rust_literal(primitive, default), to_owned),
+                container => {
+                    let elements: Vec<String> = default
+                        .trim()
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|element| !element.is_empty())
+                        .map(|element| format!("{}{}", rust_literal(primitive, element), to_owned))
+                        .collect();
+                    match container {
+                        Container::Array(_) => format!("[{}]", elements.join(", ")),
+                        _ => format!("vec![{}]", elements.join(", ")),
+                    }
+                }
+            }
+        }
+        _ => match field.container {
+            Container::Array(_) => "::std::array::from_fn(|_| Default::default())".to_string(),
+            _ => "Default::default()".to_string(),
+        },
+    }
+}
+
+fn read_field(field: &FieldDef) -> String {
+    let getter = format!(
+        "reader.get_{}()",
+        camel_to_snake(&snake_to_camel(&field.name, false))
+    );
+    let value = if is_bytes(field) {
+        format!("{}?.to_vec()", getter)
+    } else {
+        match (&field.base, field.container) {
+            (BaseType::Message(_), Container::Single) => {
+                format!("{}::read({}?)?", rust_base(&field.base), getter)
+            }
+            (
+                BaseType::Primitive(Primitive::String) | BaseType::BoundedString(_),
+                Container::Single,
+            ) => {
+                format!("{}?.to_string()?", getter)
+            }
+            (_, Container::Single) => getter,
+            (BaseType::Message(_), _) => format!(
+                "{}?.iter().map({}::read).collect::<::capnp::Result<_>>()?",
+                getter,
+                rust_base(&field.base)
+            ),
+            (BaseType::Primitive(Primitive::String) | BaseType::BoundedString(_), _) => format!(
+                "{}?.iter().map(|s| Ok(s?.to_string()?)).collect::<::capnp::Result<_>>()?",
+                getter
+            ),
+            (_, _) => format!("{}?.iter().collect()", getter),
+        }
+    };
+    match field.container {
+        Container::Array(_) => format!("super::fixed({}, {:?})?", value, field.name),
+        _ => value,
+    }
+}
+
+fn write_field(out: &mut String, field: &FieldDef) {
+    let name = camel_to_snake(&snake_to_camel(&field.name, false));
+    let value = format!("self.{}", rust_ident(&field.name));
+    if is_bytes(field) {
+        writeln!(out, "            builder.set_{}(&{}[..]);", name, value).unwrap();
+        return;
+    }
+    let is_string = matches!(
+        field.base,
+        BaseType::Primitive(Primitive::String) | BaseType::BoundedString(_)
+    );
+    match (&field.base, field.container) {
+        (BaseType::Message(_), Container::Single) => writeln!(
+            out,
+            "            {}.write(builder.reborrow().init_{}());",
+            value, name
+        ),
+        (_, Container::Single) if is_string => {
+            writeln!(out, "            builder.set_{}(&{});", name, value)
+        }
+        (_, Container::Single) => writeln!(out, "            builder.set_{}({});", name, value),
+        (base, _) => {
+            let element = match base {
+                BaseType::Message(_) => "v.write(list.reborrow().get(i as u32))",
+                _ if is_string => "list.set(i as u32, v)",
+                _ => "list.set(i as u32, *v)",
+            };
+            writeln!(
+                out,
+                "            let mut list = builder.reborrow().init_{}({}.len() as u32);\n            \
+                 for (i, v) in {}.iter().enumerate() {{\n                {};\n            }}",
+                name, value, value, element
+            )
+        }
+    }
+    .unwrap();
+}
+
+fn rust_message(out: &mut String, message: &MessageDef) {
+    let name = split(&message.name).1;
+    let module = capnp_module(&message.name);
+
+    // Default can only be derived without .msg defaults, and serde style arrays of up to 32
+    let derive_default = message.fields.iter().all(|field| {
+        field.default.is_none() && !matches!(field.container, Container::Array(size) if size > 32)
+    });
+
+    writeln!(out, "\n    // {}", message.name).unwrap();
+    let derives = if derive_default {
+        "Deserialize, Serialize, PartialEq, Clone, Debug, Default"
+    } else {
+        "Deserialize, Serialize, PartialEq, Clone, Debug"
+    };
+    writeln!(out, "    #[derive({})]", derives).unwrap();
+    writeln!(out, "    pub struct {} {{", name).unwrap();
+    if message.fields.is_empty() {
+        writeln!(out, "        pub structure_needs_at_least_one_member: u8,").unwrap();
+    }
+    for field in &message.fields {
+        if let Container::Array(_) = field.container {
+            writeln!(out, "        #[serde(with = \"super::fixed_array\")]").unwrap();
+        }
+        writeln!(
+            out,
+            "        pub {}: {},",
+            rust_ident(&field.name),
+            rust_type(field)
+        )
+        .unwrap();
+    }
+    writeln!(out, "    }}").unwrap();
+
+    if !derive_default {
+        writeln!(out, "\n    impl Default for {} {{", name).unwrap();
+        writeln!(out, "        fn default() -> Self {{").unwrap();
+        writeln!(out, "            Self {{").unwrap();
+        for field in &message.fields {
+            writeln!(
+                out,
+                "                {}: {},",
+                rust_ident(&field.name),
+                rust_default(field)
+            )
+            .unwrap();
+        }
+        writeln!(out, "            }}\n        }}\n    }}").unwrap();
+    }
+
+    writeln!(out, "\n    impl {} {{", name).unwrap();
+    for constant in &message.constants {
+        let ty = match constant.primitive {
+            Primitive::String => "&str",
+            primitive => rust_primitive(primitive),
+        };
+        writeln!(
+            out,
+            "        pub const {}: {} = {};",
+            constant.name,
+            ty,
+            rust_literal(constant.primitive, &constant.value)
+        )
+        .unwrap();
+    }
+    if !message.constants.is_empty() {
+        writeln!(out).unwrap();
+    }
+
+    let reader = if message.fields.is_empty() {
+        "_reader"
+    } else {
+        "reader"
+    };
+    writeln!(
+        out,
+        "        pub fn read({}: {}::Reader) -> ::capnp::Result<Self> {{",
+        reader, module
+    )
+    .unwrap();
+    writeln!(out, "            Ok(Self {{").unwrap();
+    if message.fields.is_empty() {
+        writeln!(
+            out,
+            "                structure_needs_at_least_one_member: 0,"
+        )
+        .unwrap();
+    }
+    for field in &message.fields {
+        writeln!(
+            out,
+            "                {}: {},",
+            rust_ident(&field.name),
+            read_field(field)
+        )
+        .unwrap();
+    }
+    writeln!(out, "            }})\n        }}\n").unwrap();
+
+    let builder = if message.fields.is_empty() {
+        "_builder"
+    } else {
+        "mut builder"
+    };
+    writeln!(
+        out,
+        "        pub fn write(&self, {}: {}::Builder) {{",
+        builder, module
+    )
+    .unwrap();
+    for field in &message.fields {
+        write_field(out, field);
+    }
+    writeln!(out, "        }}\n    }}").unwrap();
+}
+
+// Serde structs mirroring each message, CDR-compatible through the `cdr` crate,
+// with conversions from and to the generated Cap'n Proto structs
+pub fn rust_types(registry: &TypeRegistry) -> String {
+    let mut out = String::new();
+    writeln!(out, "// {}", HEADER).unwrap();
+    out.push_str(
+        r#"
+// Fixed size arrays are CDR sequences without a length prefix, serialized as tuples
+// since serde only implements arrays of up to 32 elements
+mod fixed_array {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+    pub fn serialize<S, T, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in array {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+        type Value = [T; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an array of {} elements", N)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; N], A::Error> {
+            let mut elements = Vec::with_capacity(N);
+            for i in 0..N {
+                elements.push(
+                    seq.next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?,
+                );
+            }
+            Ok(elements
+                .try_into()
+                .unwrap_or_else(|_| unreachable!("length checked above")))
+        }
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+    }
+}
+
+fn fixed<T, const N: usize>(elements: Vec<T>, field: &str) -> ::capnp::Result<[T; N]> {
+    let len = elements.len();
+    elements.try_into().map_err(|_| {
+        ::capnp::Error::failed(format!("{} expects {} elements, got {}", field, N, len))
+    })
+}
+"#,
+    );
+
+    for (package, messages) in messages_by_package(registry) {
+        writeln!(out, "\npub mod {} {{", package).unwrap();
+        writeln!(out, "    use serde::{{Deserialize, Serialize}};").unwrap();
+        for message in messages {
+            rust_message(&mut out, message);
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    out
+}
@@ -0,0 +1,42 @@
+// Cap'n Proto schema shared by the router and the client, together with the ROS types
+// generated from msgs/ and their capnp and CDR conversions
+
+use serde::{Deserialize, Serialize};
+
+#[allow(unused_parens)]
+pub mod schema_capnp {
+    include!("rpc/schema_capnp.rs");
+}
+
+#[allow(unused_parens)]
+pub mod ros_capnp {
+    include!("rpc/ros_capnp.rs");
+}
+
+pub mod ros_types {
+    include!("rpc/ros_types.rs");
+}
+
+pub mod ros_idl;
+#[cfg(test)]
+mod schema_tests;
+
+pub use ros_types::geometry_msgs::{Twist, Vector3};
+
+// Payload of HelloService, CDR encoded the same as std_msgs/String
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+pub struct Hello {
+    pub msg: String,
+}
+
+impl Hello {
+    pub fn read(reader: schema_capnp::hello::Reader) -> capnp::Result<Self> {
+        Ok(Self {
+            msg: reader.get_msg()?.to_string()?,
+        })
+    }
+
+    pub fn write(&self, mut builder: schema_capnp::hello::Builder) {
+        builder.set_msg(&self.msg);
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+// Primitive types of the ROS 2 interface definition language
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Primitive {
+    Bool,
+    Byte,
+    Char,
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Int64,
+    UInt64,
+    Float32,
+    Float64,
+    String,
+}
+
+impl Primitive {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Self::Bool,
+            "byte" => Self::Byte,
+            "char" => Self::Char,
+            "int8" => Self::Int8,
+            "uint8" => Self::UInt8,
+            "int16" => Self::Int16,
+            "uint16" => Self::UInt16,
+            "int32" => Self::Int32,
+            "uint32" => Self::UInt32,
+            "int64" => Self::Int64,
+            "uint64" => Self::UInt64,
+            "float32" => Self::Float32,
+            "float64" => Self::Float64,
+            "string" => Self::String,
+            _ => return None,
+        })
+    }
+
+    // CDR alignment and size of the primitive, strings are aligned on their length prefix
+    pub fn alignment(self) -> usize {
+        match self {
+            Self::Bool | Self::Byte | Self::Char | Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float32 | Self::String => 4,
+            Self::Int64 | Self::UInt64 | Self::Float64 => 8,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum BaseType {
+    Primitive(Primitive),
+    // Upper bound of a `string<=N`
+    BoundedString(usize),
+    // Fully qualified name, e.g. `geometry_msgs/msg/Vector3`
+    Message(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Container {
+    Single,
+    Array(usize),
+    Sequence(Option<usize>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldDef {
+    pub name: String,
+    pub base: BaseType,
+    pub container: Container,
+    pub default: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConstantDef {
+    pub name: String,
+    pub primitive: Primitive,
+    pub value: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MessageDef {
+    // Fully qualified name, e.g. `turtlesim/srv/Spawn_Request`
+    pub name: String,
+    pub fields: Vec<FieldDef>,
+    pub constants: Vec<ConstantDef>,
+}
+
+#[derive(Debug)]
+pub struct IdlError(pub String);
+
+impl fmt::Display for IdlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for IdlError {}
+
+// Splits `pkg/Name` or `pkg/msg/Name` into package and type name
+fn split_type_name(name: &str) -> Option<(&str, &str)> {
+    let mut parts = name.split('/');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(package), Some(name), None, None) => Some((package, name)),
+        (Some(package), Some(_), Some(name), None) => Some((package, name)),
+        _ => None,
+    }
+}
+
+fn parse_field_type(package: &str, spec: &str) -> Result<(BaseType, Container), IdlError> {
+    let (base, container) = match spec.find('[') {
+        Some(open) => {
+            let bound = spec[open + 1..]
+                .strip_suffix(']')
+                .ok_or_else(|| IdlError(format!("Malformed array type {}", spec)))?;
+            let container = if bound.is_empty() {
+                Container::Sequence(None)
+            } else if let Some(max) = bound.strip_prefix("<=") {
+                Container::Sequence(Some(
+                    max.parse()
+                        .map_err(|_| IdlError(format!("Malformed sequence bound in {}", spec)))?,
+                ))
+            } else {
+                Container::Array(
+                    bound
+                        .parse()
+                        .map_err(|_| IdlError(format!("Malformed array size in {}", spec)))?,
+                )
+            };
+            (&spec[..open], container)
+        }
+        None => (spec, Container::Single),
+    };
+
+    let base = if let Some(bound) = base.strip_prefix("string<=") {
+        BaseType::BoundedString(
+            bound
+                .parse()
+                .map_err(|_| IdlError(format!("Malformed string bound in {}", spec)))?,
+        )
+    } else if let Some(primitive) = Primitive::parse(base) {
+        BaseType::Primitive(primitive)
+    } else if let Some((other, name)) = split_type_name(base) {
+        BaseType::Message(format!("{}/msg/{}", other, name))
+    } else if base == "Header" {
+        // ROS 1 shorthand still found in some definitions
+        BaseType::Message("std_msgs/msg/Header".to_string())
+    } else {
+        BaseType::Message(format!("{}/msg/{}", package, base))
+    };
+    Ok((base, container))
+}
+
+// Parses the body of a .msg file, or one section of a .srv/.action file
+pub fn parse_message(name: &str, source: &str) -> Result<MessageDef, IdlError> {
+    let (package, _) =
+        split_type_name(name).ok_or_else(|| IdlError(format!("Invalid type name {}", name)))?;
+
+    let mut fields = Vec::new();
+    let mut constants = Vec::new();
+    for (number, line) in source.lines().enumerate() {
+        let line = match line.find('#') {
+            // A '#' inside a string constant is not a comment
+            Some(hash) if !line[..hash].contains('"') && !line[..hash].contains('\'') => {
+                &line[..hash]
+            }
+            _ => line,
+        }
+        .trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |msg: &str| IdlError(format!("{}:{}: {}", name, number + 1, msg));
+        let (spec, rest) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| error("expected a type and a name"))?;
+        let rest = rest.trim();
+
+        // `TYPE NAME=VALUE` is a constant, `TYPE NAME VALUE` a field with a default
+        let constant = rest
+            .split_once('=')
+            .filter(|(name, _)| !name.trim().contains(char::is_whitespace));
+        if let Some((constant, value)) = constant {
+            let primitive = match parse_field_type(package, spec)? {
+                (BaseType::Primitive(primitive), Container::Single) => primitive,
+                (BaseType::BoundedString(_), Container::Single) => Primitive::String,
+                _ => return Err(error("constants must have a primitive type")),
+            };
+            constants.push(ConstantDef {
+                name: constant.trim().to_string(),
+                primitive,
+                value: value.trim().to_string(),
+            });
+        } else {
+            let (field, default) = match rest.split_once(char::is_whitespace) {
+                Some((field, default)) => (field, Some(default.trim().to_string())),
+                None => (rest, None),
+            };
+            let (base, container) = parse_field_type(package, spec)?;
+            fields.push(FieldDef {
+                name: field.to_string(),
+                base,
+                container,
+                default,
+            });
+        }
+    }
+
+    Ok(MessageDef {
+        name: name.to_string(),
+        fields,
+        constants,
+    })
+}
+
+// All message types known to the router, keyed by fully qualified name
+#[derive(Default)]
+pub struct TypeRegistry {
+    messages: HashMap<String, MessageDef>,
+}
+
+impl TypeRegistry {
+    // Loads every `<package>/{msg,srv,action}/*` definition found under `dir`
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut registry = Self::default();
+        for package in std::fs::read_dir(dir)? {
+            let package = package?;
+            if !package.file_type()?.is_dir() {
+                continue;
+            }
+            let package_name = package.file_name().to_string_lossy().to_string();
+            for kind in ["msg", "srv", "action"] {
+                let kind_dir = package.path().join(kind);
+                if !kind_dir.is_dir() {
+                    continue;
+                }
+                for file in std::fs::read_dir(kind_dir)? {
+                    let path = file?.path();
+                    let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string())
+                    else {
+                        continue;
+                    };
+                    let source = std::fs::read_to_string(&path)?;
+                    let name = format!("{}/{}/{}", package_name, kind, stem);
+                    registry.add_interface(&name, &source)?;
+                }
+            }
+        }
+        registry.check()?;
+        Ok(registry)
+    }
+
+    // Adds a .msg, .srv or .action definition. Services and actions are split on
+    // `---` into their `_Request`/`_Response` and `_Goal`/`_Result`/`_Feedback` messages.
+    pub fn add_interface(&mut self, name: &str, source: &str) -> Result<(), IdlError> {
+        let kind = name.split('/').nth(1).unwrap_or_default();
+        let suffixes: &[&str] = match kind {
+            "msg" => &[""],
+            "srv" => &["_Request", "_Response"],
+            "action" => &["_Goal", "_Result", "_Feedback"],
+            _ => return Err(IdlError(format!("Unknown interface kind in {}", name))),
+        };
+
+        let mut sections = vec![String::new()];
+        for line in source.lines() {
+            if line.trim() == "---" {
+                sections.push(String::new());
+            } else {
+                let section = sections.last_mut().unwrap();
+                section.push_str(line);
+                section.push('\n');
+            }
+        }
+        if sections.len() != suffixes.len() {
+            return Err(IdlError(format!(
+                "{} has {} sections, expected {}",
+                name,
+                sections.len(),
+                suffixes.len()
+            )));
+        }
+        for (section, suffix) in sections.iter().zip(suffixes) {
+            let full_name = format!("{}{}", name, suffix);
+            let message = parse_message(&full_name, section)?;
+            self.messages.insert(full_name, message);
+        }
+        Ok(())
+    }
+
+    // Looks a message up by `pkg/msg/Name` or the short `pkg/Name`
+    pub fn get(&self, name: &str) -> Option<&MessageDef> {
+        self.messages.get(name).or_else(|| {
+            let (package, short) = split_type_name(name)?;
+            self.messages.get(&format!("{}/msg/{}", package, short))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &MessageDef> {
+        self.messages.values()
+    }
+
+    // Verifies that every nested type is defined
+    fn check(&self) -> Result<(), IdlError> {
+        for message in self.messages() {
+            for field in &message.fields {
+                if let BaseType::Message(nested) = &field.base
+                    && self.get(nested).is_none()
+                {
+                    return Err(IdlError(format!(
+                        "{}.{} refers to unknown type {}",
+                        message.name, field.name, nested
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,960 @@
+// Generated from the ROS message definitions in msgs/, do not edit
+
+// Fixed size arrays are CDR sequences without a length prefix, serialized as tuples
+// since serde only implements arrays of up to 32 elements
+mod fixed_array {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+    pub fn serialize<S, T, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in array {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+        type Value = [T; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an array of {} elements", N)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; N], A::Error> {
+            let mut elements = Vec::with_capacity(N);
+            for i in 0..N {
+                elements.push(
+                    seq.next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?,
+                );
+            }
+            Ok(elements
+                .try_into()
+                .unwrap_or_else(|_| unreachable!("length checked above")))
+        }
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+    }
+}
+
+fn fixed<T, const N: usize>(elements: Vec<T>, field: &str) -> ::capnp::Result<[T; N]> {
+    let len = elements.len();
+    elements.try_into().map_err(|_| {
+        ::capnp::Error::failed(format!("{} expects {} elements, got {}", field, N, len))
+    })
+}
+
+pub mod builtin_interfaces {
+    use serde::{Deserialize, Serialize};
+
+    // builtin_interfaces/msg/Duration
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Duration {
+        pub sec: i32,
+        pub nanosec: u32,
+    }
+
+    impl Duration {
+        pub fn read(reader: crate::ros_capnp::builtin_interfaces::duration::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                sec: reader.get_sec(),
+                nanosec: reader.get_nanosec(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::builtin_interfaces::duration::Builder) {
+            builder.set_sec(self.sec);
+            builder.set_nanosec(self.nanosec);
+        }
+    }
+
+    // builtin_interfaces/msg/Time
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Time {
+        pub sec: i32,
+        pub nanosec: u32,
+    }
+
+    impl Time {
+        pub fn read(reader: crate::ros_capnp::builtin_interfaces::time::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                sec: reader.get_sec(),
+                nanosec: reader.get_nanosec(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::builtin_interfaces::time::Builder) {
+            builder.set_sec(self.sec);
+            builder.set_nanosec(self.nanosec);
+        }
+    }
+}
+
+pub mod geometry_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // geometry_msgs/msg/Point
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+    }
+
+    impl Point {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::point::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                x: reader.get_x(),
+                y: reader.get_y(),
+                z: reader.get_z(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::point::Builder) {
+            builder.set_x(self.x);
+            builder.set_y(self.y);
+            builder.set_z(self.z);
+        }
+    }
+
+    // geometry_msgs/msg/Pose
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Pose {
+        pub position: super::geometry_msgs::Point,
+        pub orientation: super::geometry_msgs::Quaternion,
+    }
+
+    impl Pose {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::pose::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                position: super::geometry_msgs::Point::read(reader.get_position()?)?,
+                orientation: super::geometry_msgs::Quaternion::read(reader.get_orientation()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::pose::Builder) {
+            self.position.write(builder.reborrow().init_position());
+            self.orientation.write(builder.reborrow().init_orientation());
+        }
+    }
+
+    // geometry_msgs/msg/PoseStamped
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct PoseStamped {
+        pub header: super::std_msgs::Header,
+        pub pose: super::geometry_msgs::Pose,
+    }
+
+    impl PoseStamped {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::pose_stamped::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                pose: super::geometry_msgs::Pose::read(reader.get_pose()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::pose_stamped::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            self.pose.write(builder.reborrow().init_pose());
+        }
+    }
+
+    // geometry_msgs/msg/PoseWithCovariance
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+    pub struct PoseWithCovariance {
+        pub pose: super::geometry_msgs::Pose,
+        #[serde(with = "super::fixed_array")]
+        pub covariance: [f64; 36],
+    }
+
+    impl Default for PoseWithCovariance {
+        fn default() -> Self {
+            Self {
+                pose: Default::default(),
+                covariance: ::std::array::from_fn(|_| Default::default()),
+            }
+        }
+    }
+
+    impl PoseWithCovariance {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::pose_with_covariance::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                pose: super::geometry_msgs::Pose::read(reader.get_pose()?)?,
+                covariance: super::fixed(reader.get_covariance()?.iter().collect(), "covariance")?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::pose_with_covariance::Builder) {
+            self.pose.write(builder.reborrow().init_pose());
+            let mut list = builder.reborrow().init_covariance(self.covariance.len() as u32);
+            for (i, v) in self.covariance.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+        }
+    }
+
+    // geometry_msgs/msg/Quaternion
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+    pub struct Quaternion {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+        pub w: f64,
+    }
+
+    impl Default for Quaternion {
+        fn default() -> Self {
+            Self {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0,
+            }
+        }
+    }
+
+    impl Quaternion {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::quaternion::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                x: reader.get_x(),
+                y: reader.get_y(),
+                z: reader.get_z(),
+                w: reader.get_w(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::quaternion::Builder) {
+            builder.set_x(self.x);
+            builder.set_y(self.y);
+            builder.set_z(self.z);
+            builder.set_w(self.w);
+        }
+    }
+
+    // geometry_msgs/msg/Transform
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Transform {
+        pub translation: super::geometry_msgs::Vector3,
+        pub rotation: super::geometry_msgs::Quaternion,
+    }
+
+    impl Transform {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::transform::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                translation: super::geometry_msgs::Vector3::read(reader.get_translation()?)?,
+                rotation: super::geometry_msgs::Quaternion::read(reader.get_rotation()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::transform::Builder) {
+            self.translation.write(builder.reborrow().init_translation());
+            self.rotation.write(builder.reborrow().init_rotation());
+        }
+    }
+
+    // geometry_msgs/msg/TransformStamped
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct TransformStamped {
+        pub header: super::std_msgs::Header,
+        pub child_frame_id: ::std::string::String,
+        pub transform: super::geometry_msgs::Transform,
+    }
+
+    impl TransformStamped {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::transform_stamped::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                child_frame_id: reader.get_child_frame_id()?.to_string()?,
+                transform: super::geometry_msgs::Transform::read(reader.get_transform()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::transform_stamped::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            builder.set_child_frame_id(&self.child_frame_id);
+            self.transform.write(builder.reborrow().init_transform());
+        }
+    }
+
+    // geometry_msgs/msg/Twist
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Twist {
+        pub linear: super::geometry_msgs::Vector3,
+        pub angular: super::geometry_msgs::Vector3,
+    }
+
+    impl Twist {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::twist::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                linear: super::geometry_msgs::Vector3::read(reader.get_linear()?)?,
+                angular: super::geometry_msgs::Vector3::read(reader.get_angular()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::twist::Builder) {
+            self.linear.write(builder.reborrow().init_linear());
+            self.angular.write(builder.reborrow().init_angular());
+        }
+    }
+
+    // geometry_msgs/msg/TwistStamped
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct TwistStamped {
+        pub header: super::std_msgs::Header,
+        pub twist: super::geometry_msgs::Twist,
+    }
+
+    impl TwistStamped {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::twist_stamped::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                twist: super::geometry_msgs::Twist::read(reader.get_twist()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::twist_stamped::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            self.twist.write(builder.reborrow().init_twist());
+        }
+    }
+
+    // geometry_msgs/msg/TwistWithCovariance
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+    pub struct TwistWithCovariance {
+        pub twist: super::geometry_msgs::Twist,
+        #[serde(with = "super::fixed_array")]
+        pub covariance: [f64; 36],
+    }
+
+    impl Default for TwistWithCovariance {
+        fn default() -> Self {
+            Self {
+                twist: Default::default(),
+                covariance: ::std::array::from_fn(|_| Default::default()),
+            }
+        }
+    }
+
+    impl TwistWithCovariance {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::twist_with_covariance::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                twist: super::geometry_msgs::Twist::read(reader.get_twist()?)?,
+                covariance: super::fixed(reader.get_covariance()?.iter().collect(), "covariance")?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::twist_with_covariance::Builder) {
+            self.twist.write(builder.reborrow().init_twist());
+            let mut list = builder.reborrow().init_covariance(self.covariance.len() as u32);
+            for (i, v) in self.covariance.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+        }
+    }
+
+    // geometry_msgs/msg/Vector3
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Vector3 {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+    }
+
+    impl Vector3 {
+        pub fn read(reader: crate::ros_capnp::geometry_msgs::vector3::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                x: reader.get_x(),
+                y: reader.get_y(),
+                z: reader.get_z(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::geometry_msgs::vector3::Builder) {
+            builder.set_x(self.x);
+            builder.set_y(self.y);
+            builder.set_z(self.z);
+        }
+    }
+}
+
+pub mod nav_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // nav_msgs/msg/Odometry
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Odometry {
+        pub header: super::std_msgs::Header,
+        pub child_frame_id: ::std::string::String,
+        pub pose: super::geometry_msgs::PoseWithCovariance,
+        pub twist: super::geometry_msgs::TwistWithCovariance,
+    }
+
+    impl Odometry {
+        pub fn read(reader: crate::ros_capnp::nav_msgs::odometry::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                child_frame_id: reader.get_child_frame_id()?.to_string()?,
+                pose: super::geometry_msgs::PoseWithCovariance::read(reader.get_pose()?)?,
+                twist: super::geometry_msgs::TwistWithCovariance::read(reader.get_twist()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::nav_msgs::odometry::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            builder.set_child_frame_id(&self.child_frame_id);
+            self.pose.write(builder.reborrow().init_pose());
+            self.twist.write(builder.reborrow().init_twist());
+        }
+    }
+
+    // nav_msgs/msg/Path
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Path {
+        pub header: super::std_msgs::Header,
+        pub poses: ::std::vec::Vec<super::geometry_msgs::PoseStamped>,
+    }
+
+    impl Path {
+        pub fn read(reader: crate::ros_capnp::nav_msgs::path::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                poses: reader.get_poses()?.iter().map(super::geometry_msgs::PoseStamped::read).collect::<::capnp::Result<_>>()?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::nav_msgs::path::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            let mut list = builder.reborrow().init_poses(self.poses.len() as u32);
+            for (i, v) in self.poses.iter().enumerate() {
+                v.write(list.reborrow().get(i as u32));
+            }
+        }
+    }
+}
+
+pub mod rosgraph_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // rosgraph_msgs/msg/Clock
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Clock {
+        pub clock: super::builtin_interfaces::Time,
+    }
+
+    impl Clock {
+        pub fn read(reader: crate::ros_capnp::rosgraph_msgs::clock::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                clock: super::builtin_interfaces::Time::read(reader.get_clock()?)?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::rosgraph_msgs::clock::Builder) {
+            self.clock.write(builder.reborrow().init_clock());
+        }
+    }
+}
+
+pub mod sensor_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // sensor_msgs/msg/BatteryState
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct BatteryState {
+        pub header: super::std_msgs::Header,
+        pub voltage: f32,
+        pub temperature: f32,
+        pub current: f32,
+        pub charge: f32,
+        pub capacity: f32,
+        pub design_capacity: f32,
+        pub percentage: f32,
+        pub power_supply_status: u8,
+        pub power_supply_health: u8,
+        pub power_supply_technology: u8,
+        pub present: bool,
+        pub cell_voltage: ::std::vec::Vec<f32>,
+        pub cell_temperature: ::std::vec::Vec<f32>,
+        pub location: ::std::string::String,
+        pub serial_number: ::std::string::String,
+    }
+
+    impl BatteryState {
+        pub const POWER_SUPPLY_STATUS_UNKNOWN: u8 = 0;
+        pub const POWER_SUPPLY_STATUS_CHARGING: u8 = 1;
+        pub const POWER_SUPPLY_STATUS_DISCHARGING: u8 = 2;
+        pub const POWER_SUPPLY_STATUS_NOT_CHARGING: u8 = 3;
+        pub const POWER_SUPPLY_STATUS_FULL: u8 = 4;
+        pub const POWER_SUPPLY_HEALTH_UNKNOWN: u8 = 0;
+        pub const POWER_SUPPLY_HEALTH_GOOD: u8 = 1;
+        pub const POWER_SUPPLY_HEALTH_OVERHEAT: u8 = 2;
+        pub const POWER_SUPPLY_HEALTH_DEAD: u8 = 3;
+        pub const POWER_SUPPLY_HEALTH_OVERVOLTAGE: u8 = 4;
+        pub const POWER_SUPPLY_HEALTH_UNSPEC_FAILURE: u8 = 5;
+        pub const POWER_SUPPLY_HEALTH_COLD: u8 = 6;
+        pub const POWER_SUPPLY_HEALTH_WATCHDOG_TIMER_EXPIRE: u8 = 7;
+        pub const POWER_SUPPLY_HEALTH_SAFETY_TIMER_EXPIRE: u8 = 8;
+        pub const POWER_SUPPLY_TECHNOLOGY_UNKNOWN: u8 = 0;
+        pub const POWER_SUPPLY_TECHNOLOGY_NIMH: u8 = 1;
+        pub const POWER_SUPPLY_TECHNOLOGY_LION: u8 = 2;
+        pub const POWER_SUPPLY_TECHNOLOGY_LIPO: u8 = 3;
+        pub const POWER_SUPPLY_TECHNOLOGY_LIFE: u8 = 4;
+        pub const POWER_SUPPLY_TECHNOLOGY_NICD: u8 = 5;
+        pub const POWER_SUPPLY_TECHNOLOGY_LIMN: u8 = 6;
+
+        pub fn read(reader: crate::ros_capnp::sensor_msgs::battery_state::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                voltage: reader.get_voltage(),
+                temperature: reader.get_temperature(),
+                current: reader.get_current(),
+                charge: reader.get_charge(),
+                capacity: reader.get_capacity(),
+                design_capacity: reader.get_design_capacity(),
+                percentage: reader.get_percentage(),
+                power_supply_status: reader.get_power_supply_status(),
+                power_supply_health: reader.get_power_supply_health(),
+                power_supply_technology: reader.get_power_supply_technology(),
+                present: reader.get_present(),
+                cell_voltage: reader.get_cell_voltage()?.iter().collect(),
+                cell_temperature: reader.get_cell_temperature()?.iter().collect(),
+                location: reader.get_location()?.to_string()?,
+                serial_number: reader.get_serial_number()?.to_string()?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::sensor_msgs::battery_state::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            builder.set_voltage(self.voltage);
+            builder.set_temperature(self.temperature);
+            builder.set_current(self.current);
+            builder.set_charge(self.charge);
+            builder.set_capacity(self.capacity);
+            builder.set_design_capacity(self.design_capacity);
+            builder.set_percentage(self.percentage);
+            builder.set_power_supply_status(self.power_supply_status);
+            builder.set_power_supply_health(self.power_supply_health);
+            builder.set_power_supply_technology(self.power_supply_technology);
+            builder.set_present(self.present);
+            let mut list = builder.reborrow().init_cell_voltage(self.cell_voltage.len() as u32);
+            for (i, v) in self.cell_voltage.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            let mut list = builder.reborrow().init_cell_temperature(self.cell_temperature.len() as u32);
+            for (i, v) in self.cell_temperature.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            builder.set_location(&self.location);
+            builder.set_serial_number(&self.serial_number);
+        }
+    }
+
+    // sensor_msgs/msg/CompressedImage
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct CompressedImage {
+        pub header: super::std_msgs::Header,
+        pub format: ::std::string::String,
+        pub data: ::std::vec::Vec<u8>,
+    }
+
+    impl CompressedImage {
+        pub fn read(reader: crate::ros_capnp::sensor_msgs::compressed_image::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                format: reader.get_format()?.to_string()?,
+                data: reader.get_data()?.to_vec(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::sensor_msgs::compressed_image::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            builder.set_format(&self.format);
+            builder.set_data(&self.data[..]);
+        }
+    }
+
+    // sensor_msgs/msg/Image
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Image {
+        pub header: super::std_msgs::Header,
+        pub height: u32,
+        pub width: u32,
+        pub encoding: ::std::string::String,
+        pub is_bigendian: u8,
+        pub step: u32,
+        pub data: ::std::vec::Vec<u8>,
+    }
+
+    impl Image {
+        pub fn read(reader: crate::ros_capnp::sensor_msgs::image::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                height: reader.get_height(),
+                width: reader.get_width(),
+                encoding: reader.get_encoding()?.to_string()?,
+                is_bigendian: reader.get_is_bigendian(),
+                step: reader.get_step(),
+                data: reader.get_data()?.to_vec(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::sensor_msgs::image::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            builder.set_height(self.height);
+            builder.set_width(self.width);
+            builder.set_encoding(&self.encoding);
+            builder.set_is_bigendian(self.is_bigendian);
+            builder.set_step(self.step);
+            builder.set_data(&self.data[..]);
+        }
+    }
+
+    // sensor_msgs/msg/Imu
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Imu {
+        pub header: super::std_msgs::Header,
+        pub orientation: super::geometry_msgs::Quaternion,
+        #[serde(with = "super::fixed_array")]
+        pub orientation_covariance: [f64; 9],
+        pub angular_velocity: super::geometry_msgs::Vector3,
+        #[serde(with = "super::fixed_array")]
+        pub angular_velocity_covariance: [f64; 9],
+        pub linear_acceleration: super::geometry_msgs::Vector3,
+        #[serde(with = "super::fixed_array")]
+        pub linear_acceleration_covariance: [f64; 9],
+    }
+
+    impl Imu {
+        pub fn read(reader: crate::ros_capnp::sensor_msgs::imu::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                orientation: super::geometry_msgs::Quaternion::read(reader.get_orientation()?)?,
+                orientation_covariance: super::fixed(reader.get_orientation_covariance()?.iter().collect(), "orientation_covariance")?,
+                angular_velocity: super::geometry_msgs::Vector3::read(reader.get_angular_velocity()?)?,
+                angular_velocity_covariance: super::fixed(reader.get_angular_velocity_covariance()?.iter().collect(), "angular_velocity_covariance")?,
+                linear_acceleration: super::geometry_msgs::Vector3::read(reader.get_linear_acceleration()?)?,
+                linear_acceleration_covariance: super::fixed(reader.get_linear_acceleration_covariance()?.iter().collect(), "linear_acceleration_covariance")?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::sensor_msgs::imu::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            self.orientation.write(builder.reborrow().init_orientation());
+            let mut list = builder.reborrow().init_orientation_covariance(self.orientation_covariance.len() as u32);
+            for (i, v) in self.orientation_covariance.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            self.angular_velocity.write(builder.reborrow().init_angular_velocity());
+            let mut list = builder.reborrow().init_angular_velocity_covariance(self.angular_velocity_covariance.len() as u32);
+            for (i, v) in self.angular_velocity_covariance.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            self.linear_acceleration.write(builder.reborrow().init_linear_acceleration());
+            let mut list = builder.reborrow().init_linear_acceleration_covariance(self.linear_acceleration_covariance.len() as u32);
+            for (i, v) in self.linear_acceleration_covariance.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+        }
+    }
+
+    // sensor_msgs/msg/JointState
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct JointState {
+        pub header: super::std_msgs::Header,
+        pub name: ::std::vec::Vec<::std::string::String>,
+        pub position: ::std::vec::Vec<f64>,
+        pub velocity: ::std::vec::Vec<f64>,
+        pub effort: ::std::vec::Vec<f64>,
+    }
+
+    impl JointState {
+        pub fn read(reader: crate::ros_capnp::sensor_msgs::joint_state::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                name: reader.get_name()?.iter().map(|s| Ok(s?.to_string()?)).collect::<::capnp::Result<_>>()?,
+                position: reader.get_position()?.iter().collect(),
+                velocity: reader.get_velocity()?.iter().collect(),
+                effort: reader.get_effort()?.iter().collect(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::sensor_msgs::joint_state::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            let mut list = builder.reborrow().init_name(self.name.len() as u32);
+            for (i, v) in self.name.iter().enumerate() {
+                list.set(i as u32, v);
+            }
+            let mut list = builder.reborrow().init_position(self.position.len() as u32);
+            for (i, v) in self.position.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            let mut list = builder.reborrow().init_velocity(self.velocity.len() as u32);
+            for (i, v) in self.velocity.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            let mut list = builder.reborrow().init_effort(self.effort.len() as u32);
+            for (i, v) in self.effort.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+        }
+    }
+
+    // sensor_msgs/msg/LaserScan
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct LaserScan {
+        pub header: super::std_msgs::Header,
+        pub angle_min: f32,
+        pub angle_max: f32,
+        pub angle_increment: f32,
+        pub time_increment: f32,
+        pub scan_time: f32,
+        pub range_min: f32,
+        pub range_max: f32,
+        pub ranges: ::std::vec::Vec<f32>,
+        pub intensities: ::std::vec::Vec<f32>,
+    }
+
+    impl LaserScan {
+        pub fn read(reader: crate::ros_capnp::sensor_msgs::laser_scan::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                header: super::std_msgs::Header::read(reader.get_header()?)?,
+                angle_min: reader.get_angle_min(),
+                angle_max: reader.get_angle_max(),
+                angle_increment: reader.get_angle_increment(),
+                time_increment: reader.get_time_increment(),
+                scan_time: reader.get_scan_time(),
+                range_min: reader.get_range_min(),
+                range_max: reader.get_range_max(),
+                ranges: reader.get_ranges()?.iter().collect(),
+                intensities: reader.get_intensities()?.iter().collect(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::sensor_msgs::laser_scan::Builder) {
+            self.header.write(builder.reborrow().init_header());
+            builder.set_angle_min(self.angle_min);
+            builder.set_angle_max(self.angle_max);
+            builder.set_angle_increment(self.angle_increment);
+            builder.set_time_increment(self.time_increment);
+            builder.set_scan_time(self.scan_time);
+            builder.set_range_min(self.range_min);
+            builder.set_range_max(self.range_max);
+            let mut list = builder.reborrow().init_ranges(self.ranges.len() as u32);
+            for (i, v) in self.ranges.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+            let mut list = builder.reborrow().init_intensities(self.intensities.len() as u32);
+            for (i, v) in self.intensities.iter().enumerate() {
+                list.set(i as u32, *v);
+            }
+        }
+    }
+}
+
+pub mod std_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // std_msgs/msg/ColorRGBA
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct ColorRGBA {
+        pub r: f32,
+        pub g: f32,
+        pub b: f32,
+        pub a: f32,
+    }
+
+    impl ColorRGBA {
+        pub fn read(reader: crate::ros_capnp::std_msgs::color_r_g_b_a::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                r: reader.get_r(),
+                g: reader.get_g(),
+                b: reader.get_b(),
+                a: reader.get_a(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::std_msgs::color_r_g_b_a::Builder) {
+            builder.set_r(self.r);
+            builder.set_g(self.g);
+            builder.set_b(self.b);
+            builder.set_a(self.a);
+        }
+    }
+
+    // std_msgs/msg/Empty
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Empty {
+        pub structure_needs_at_least_one_member: u8,
+    }
+
+    impl Empty {
+        pub fn read(_reader: crate::ros_capnp::std_msgs::empty::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                structure_needs_at_least_one_member: 0,
+            })
+        }
+
+        pub fn write(&self, _builder: crate::ros_capnp::std_msgs::empty::Builder) {
+        }
+    }
+
+    // std_msgs/msg/Header
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Header {
+        pub stamp: super::builtin_interfaces::Time,
+        pub frame_id: ::std::string::String,
+    }
+
+    impl Header {
+        pub fn read(reader: crate::ros_capnp::std_msgs::header::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                stamp: super::builtin_interfaces::Time::read(reader.get_stamp()?)?,
+                frame_id: reader.get_frame_id()?.to_string()?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::std_msgs::header::Builder) {
+            self.stamp.write(builder.reborrow().init_stamp());
+            builder.set_frame_id(&self.frame_id);
+        }
+    }
+
+    // std_msgs/msg/String
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct String {
+        pub data: ::std::string::String,
+    }
+
+    impl String {
+        pub fn read(reader: crate::ros_capnp::std_msgs::string::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                data: reader.get_data()?.to_string()?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::std_msgs::string::Builder) {
+            builder.set_data(&self.data);
+        }
+    }
+}
+
+pub mod tf2_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // tf2_msgs/msg/TFMessage
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct TFMessage {
+        pub transforms: ::std::vec::Vec<super::geometry_msgs::TransformStamped>,
+    }
+
+    impl TFMessage {
+        pub fn read(reader: crate::ros_capnp::tf2_msgs::t_f_message::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                transforms: reader.get_transforms()?.iter().map(super::geometry_msgs::TransformStamped::read).collect::<::capnp::Result<_>>()?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::tf2_msgs::t_f_message::Builder) {
+            let mut list = builder.reborrow().init_transforms(self.transforms.len() as u32);
+            for (i, v) in self.transforms.iter().enumerate() {
+                v.write(list.reborrow().get(i as u32));
+            }
+        }
+    }
+}
+
+pub mod turtlesim {
+    use serde::{Deserialize, Serialize};
+
+    // turtlesim/msg/Color
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Color {
+        pub r: u8,
+        pub g: u8,
+        pub b: u8,
+    }
+
+    impl Color {
+        pub fn read(reader: crate::ros_capnp::turtlesim::color::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                r: reader.get_r(),
+                g: reader.get_g(),
+                b: reader.get_b(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::turtlesim::color::Builder) {
+            builder.set_r(self.r);
+            builder.set_g(self.g);
+            builder.set_b(self.b);
+        }
+    }
+
+    // turtlesim/msg/Pose
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct Pose {
+        pub x: f32,
+        pub y: f32,
+        pub theta: f32,
+        pub linear_velocity: f32,
+        pub angular_velocity: f32,
+    }
+
+    impl Pose {
+        pub fn read(reader: crate::ros_capnp::turtlesim::pose::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                x: reader.get_x(),
+                y: reader.get_y(),
+                theta: reader.get_theta(),
+                linear_velocity: reader.get_linear_velocity(),
+                angular_velocity: reader.get_angular_velocity(),
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::turtlesim::pose::Builder) {
+            builder.set_x(self.x);
+            builder.set_y(self.y);
+            builder.set_theta(self.theta);
+            builder.set_linear_velocity(self.linear_velocity);
+            builder.set_angular_velocity(self.angular_velocity);
+        }
+    }
+}
+
+pub mod unique_identifier_msgs {
+    use serde::{Deserialize, Serialize};
+
+    // unique_identifier_msgs/msg/UUID
+    #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+    pub struct UUID {
+        #[serde(with = "super::fixed_array")]
+        pub uuid: [u8; 16],
+    }
+
+    impl UUID {
+        pub fn read(reader: crate::ros_capnp::unique_identifier_msgs::u_u_i_d::Reader) -> ::capnp::Result<Self> {
+            Ok(Self {
+                uuid: super::fixed(reader.get_uuid()?.to_vec(), "uuid")?,
+            })
+        }
+
+        pub fn write(&self, mut builder: crate::ros_capnp::unique_identifier_msgs::u_u_i_d::Builder) {
+            builder.set_uuid(&self.uuid[..]);
+        }
+    }
+}
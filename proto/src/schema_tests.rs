@@ -0,0 +1,152 @@
+// Keeps the schema wire compatible with peers built from older versions. Every field,
+// enumerant and method of the compiled schema is rendered as a line keyed by node id and
+// ordinal, and nothing in schema.lock may disappear or change. Additions have to be locked
+// too, by running the tests with UPDATE_SCHEMA_LOCK=1, so that they are reviewed
+
+use std::collections::BTreeMap;
+
+use capnp::message::ReaderOptions;
+use capnp::schema_capnp::{code_generator_request, field, node, type_};
+
+const REQUEST: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/schema.cgr"));
+const LOCK_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/schema.lock");
+
+fn type_name(ty: type_::Reader) -> capnp::Result<String> {
+    Ok(match ty.which()? {
+        type_::Void(()) => "Void".to_string(),
+        type_::Bool(()) => "Bool".to_string(),
+        type_::Int8(()) => "Int8".to_string(),
+        type_::Int16(()) => "Int16".to_string(),
+        type_::Int32(()) => "Int32".to_string(),
+        type_::Int64(()) => "Int64".to_string(),
+        type_::Uint8(()) => "UInt8".to_string(),
+        type_::Uint16(()) => "UInt16".to_string(),
+        type_::Uint32(()) => "UInt32".to_string(),
+        type_::Uint64(()) => "UInt64".to_string(),
+        type_::Float32(()) => "Float32".to_string(),
+        type_::Float64(()) => "Float64".to_string(),
+        type_::Text(()) => "Text".to_string(),
+        type_::Data(()) => "Data".to_string(),
+        type_::List(list) => format!("List({})", type_name(list.get_element_type()?)?),
+        type_::Enum(e) => format!("enum {:#x}", e.get_type_id()),
+        type_::Struct(s) => format!("struct {:#x}", s.get_type_id()),
+        type_::Interface(i) => format!("interface {:#x}", i.get_type_id()),
+        type_::AnyPointer(_) => "AnyPointer".to_string(),
+    })
+}
+
+fn field_line(id: u64, field: field::Reader) -> capnp::Result<String> {
+    let mut line = match field.which()? {
+        field::Slot(slot) => format!(
+            "{:#x} field @{} {} at {}",
+            id,
+            match field.get_ordinal().which()? {
+                field::ordinal::Explicit(ordinal) => ordinal,
+                field::ordinal::Implicit(()) => field.get_code_order(),
+            },
+            type_name(slot.get_type()?)?,
+            slot.get_offset()
+        ),
+        field::Group(group) => format!("{:#x} group {:#x}", id, group.get_type_id()),
+    };
+    if field.get_discriminant_value() != field::NO_DISCRIMINANT {
+        line += &format!(" case {}", field.get_discriminant_value());
+    }
+    Ok(line)
+}
+
+// Wire relevant lines mapped to the names they had, which are only there for readers
+fn schema_lines() -> capnp::Result<BTreeMap<String, String>> {
+    // include_bytes! gives no alignment guarantee, so the segments are copied
+    let message = capnp::serialize::read_message(REQUEST, ReaderOptions::new())?;
+    let request = message.get_root::<code_generator_request::Reader>()?;
+    let mut lines = BTreeMap::new();
+    for node in request.get_nodes()? {
+        let id = node.get_id();
+        let name = node.get_display_name()?.to_str()?;
+        // Display names are prefixed with the file they were declared in
+        let name = name.rsplit_once('/').map_or(name, |(_, name)| name);
+        match node.which()? {
+            node::Struct(st) => {
+                if st.get_discriminant_count() > 0 {
+                    lines.insert(
+                        format!("{:#x} union at {}", id, st.get_discriminant_offset()),
+                        name.to_string(),
+                    );
+                }
+                for field in st.get_fields()? {
+                    lines.insert(
+                        field_line(id, field)?,
+                        format!("{}.{}", name, field.get_name()?.to_str()?),
+                    );
+                }
+            }
+            node::Enum(e) => {
+                for (i, enumerant) in e.get_enumerants()?.iter().enumerate() {
+                    lines.insert(
+                        format!("{:#x} enumerant {}", id, i),
+                        format!("{}.{}", name, enumerant.get_name()?.to_str()?),
+                    );
+                }
+            }
+            node::Interface(interface) => {
+                lines.insert(format!("{:#x} interface", id), name.to_string());
+                for (i, method) in interface.get_methods()?.iter().enumerate() {
+                    lines.insert(
+                        format!(
+                            "{:#x} method @{} ({:#x}) -> ({:#x})",
+                            id,
+                            i,
+                            method.get_param_struct_type(),
+                            method.get_result_struct_type()
+                        ),
+                        format!("{}.{}", name, method.get_name()?.to_str()?),
+                    );
+                }
+            }
+            // Constants and annotations never go over the wire
+            _ => {}
+        }
+    }
+    Ok(lines)
+}
+
+#[test]
+fn schema_is_compatible_with_lock() {
+    let lines = schema_lines().unwrap();
+    if std::env::var("UPDATE_SCHEMA_LOCK").is_ok_and(|value| value == "1") {
+        let lock: String = lines
+            .iter()
+            .map(|(line, name)| format!("{} # {}\n", line, name))
+            .collect();
+        std::fs::write(LOCK_PATH, lock).unwrap();
+        return;
+    }
+
+    let lock = std::fs::read_to_string(LOCK_PATH).unwrap();
+    let locked: Vec<&str> = lock
+        .lines()
+        .map(|line| line.split_once(" # ").map_or(line, |(line, _)| line))
+        .collect();
+    let broken: Vec<&str> = locked
+        .iter()
+        .filter(|line| !lines.contains_key(**line))
+        .copied()
+        .collect();
+    assert!(
+        broken.is_empty(),
+        "Schema changes break wire compatibility, removed or changed:\n{}",
+        broken.join("\n")
+    );
+    let added: Vec<String> = lines
+        .iter()
+        .filter(|(line, _)| !locked.contains(&line.as_str()))
+        .map(|(line, name)| format!("{} # {}", line, name))
+        .collect();
+    assert!(
+        added.is_empty(),
+        "Schema additions are not in schema.lock, run the tests with UPDATE_SCHEMA_LOCK=1 and \
+         bump schemaVersion:\n{}",
+        added.join("\n")
+    );
+}
@@ -1,17 +1,38 @@
+import json
+import sys
+
 import zenoh
 
 
+def describe(sample):
+    # Decoded according to the encoding the router labels its payloads with
+    encoding = str(sample.encoding)
+    kind, _, schema = encoding.partition(";")
+    payload = sample.payload.to_bytes()
+    if kind == "application/cdr":
+        return f"CDR {schema or 'of unknown type'}, {len(payload)} bytes: {payload.hex()}"
+    if schema == "application/capnp-packed":
+        return f"packed Cap'n Proto, {len(payload)} bytes: {payload.hex()}"
+    if kind == "application/json":
+        return f"JSON {json.loads(payload)}"
+    if kind == "text/plain":
+        return f"text '{payload.decode()}'"
+    return f"{encoding}, {len(payload)} bytes"
+
+
 def listener(sample):
-    print(
-        f"Received {sample.kind} ('{sample.key_expr}': '{sample.payload.to_string()}')"
-    )
+    print(f"Received {sample.kind} on '{sample.key_expr}': {describe(sample)}")
+    if sample.attachment is not None:
+        # Client id, sequence number and timestamps of the router
+        print(f"  origin {json.loads(sample.attachment.to_bytes())}")
 
 
 if __name__ == "__main__":
-    print("Zenoh subscriber started. Press 'q' + Enter to quit.")
+    key = sys.argv[1] if len(sys.argv) > 1 else "rt/hello"
+    print(f"Zenoh subscriber on '{key}' started. Press 'q' + Enter to quit.")
 
     with zenoh.open(zenoh.Config()) as session:
-        sub = session.declare_subscriber("rt/hello", listener)
+        sub = session.declare_subscriber(key, listener)
 
         try:
             while True:
@@ -0,0 +1,532 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use capnp::capability::Promise;
+use cdr::{CdrLe, Infinite};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::dynamic_cdr::{self, Value};
+use crate::publisher::write_value;
+use crate::query::{SERVICE_TIMEOUT, call_service, query_service};
+use crate::ros_idl::{BaseType, Container, FieldDef, MessageDef, Primitive, TypeRegistry};
+use crate::schema_capnp::{self, generic_goal_handle, goal_feedback, goal_handle};
+
+// get_result only answers once the goal terminates, so it gets a far longer timeout
+// than the other action services. The bridge's `queries_timeout.actions.get_result`
+// must be at least as long, which is why this is wall time like SERVICE_TIMEOUT.
+pub const RESULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub type GoalId = [u8; 16];
+
+// action_msgs/msg/GoalStatus values
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GoalStatus {
+    Unknown,
+    Accepted,
+    Executing,
+    Canceling,
+    Succeeded,
+    Canceled,
+    Aborted,
+}
+
+impl GoalStatus {
+    fn from_ros(status: i8) -> Self {
+        match status {
+            1 => Self::Accepted,
+            2 => Self::Executing,
+            3 => Self::Canceling,
+            4 => Self::Succeeded,
+            5 => Self::Canceled,
+            6 => Self::Aborted,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Canceled | Self::Aborted)
+    }
+}
+
+impl From<GoalStatus> for schema_capnp::GoalStatus {
+    fn from(status: GoalStatus) -> Self {
+        match status {
+            GoalStatus::Unknown => Self::Unknown,
+            GoalStatus::Accepted => Self::Accepted,
+            GoalStatus::Executing => Self::Executing,
+            GoalStatus::Canceling => Self::Canceling,
+            GoalStatus::Succeeded => Self::Succeeded,
+            GoalStatus::Canceled => Self::Canceled,
+            GoalStatus::Aborted => Self::Aborted,
+        }
+    }
+}
+
+// builtin_interfaces/msg/Time
+#[derive(Deserialize, Serialize, PartialEq, Default, Clone)]
+pub struct Time {
+    pub sec: i32,
+    pub nanosec: u32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+struct GoalInfo {
+    goal_id: GoalId,
+    stamp: Time,
+}
+
+#[derive(Serialize)]
+struct SendGoalRequest<'a, G> {
+    goal_id: GoalId,
+    goal: &'a G,
+}
+
+#[derive(Deserialize)]
+struct SendGoalResponse {
+    accepted: bool,
+    _stamp: Time,
+}
+
+#[derive(Serialize)]
+struct GetResultRequest {
+    goal_id: GoalId,
+}
+
+#[derive(Deserialize)]
+struct GetResultResponse<R> {
+    status: i8,
+    result: R,
+}
+
+#[derive(Serialize)]
+struct CancelGoalRequest {
+    goal_info: GoalInfo,
+}
+
+#[derive(Deserialize)]
+struct CancelGoalResponse {
+    return_code: i8,
+    _goals_canceling: Vec<GoalInfo>,
+}
+
+#[derive(Deserialize)]
+struct FeedbackMessage<F> {
+    goal_id: GoalId,
+    feedback: F,
+}
+
+#[derive(Deserialize)]
+struct StatusEntry {
+    goal_info: GoalInfo,
+    status: i8,
+}
+
+#[derive(Deserialize)]
+struct GoalStatusArray {
+    status_list: Vec<StatusEntry>,
+}
+
+// unique_identifier_msgs/msg/UUID is a single uint8[16], laid out the same as the array
+fn goal_id_value(goal_id: GoalId) -> Value {
+    Value::Array(goal_id.iter().copied().map(Value::UInt8).collect())
+}
+
+fn field(name: &str, base: BaseType, container: Container) -> FieldDef {
+    FieldDef {
+        name: name.to_string(),
+        base,
+        container,
+        default: None,
+    }
+}
+
+// An action type from the registry, e.g. `turtlesim/action/RotateAbsolute`. The registry
+// only has its `_Goal`, `_Result` and `_Feedback` messages, the messages of the action
+// services that wrap them with the goal id are put together here
+#[derive(Clone)]
+pub struct DynamicAction {
+    name: String,
+    registry: Arc<TypeRegistry>,
+    send_goal_request: Arc<MessageDef>,
+    get_result_response: Arc<MessageDef>,
+    feedback_message: Arc<MessageDef>,
+}
+
+impl DynamicAction {
+    // Takes `pkg/action/Name` or the short `pkg/Name`
+    pub fn resolve(registry: Arc<TypeRegistry>, action_type: &str) -> Result<Self, capnp::Error> {
+        let name = match action_type.split('/').collect::<Vec<_>>()[..] {
+            [package, name] => format!("{}/action/{}", package, name),
+            _ => action_type.to_string(),
+        };
+        if registry.get(&format!("{}_Goal", name)).is_none() {
+            return Err(capnp::Error::failed(format!(
+                "Unknown action type {}",
+                action_type
+            )));
+        }
+
+        let goal_id = || {
+            field(
+                "goal_id",
+                BaseType::Primitive(Primitive::UInt8),
+                Container::Array(16),
+            )
+        };
+        let wrapper = |suffix: &str, fields: Vec<FieldDef>| {
+            Arc::new(MessageDef {
+                name: format!("{}{}", name, suffix),
+                fields,
+                constants: Vec::new(),
+            })
+        };
+        let message = |suffix: &str| BaseType::Message(format!("{}{}", name, suffix));
+        Ok(Self {
+            send_goal_request: wrapper(
+                "_SendGoal_Request",
+                vec![
+                    goal_id(),
+                    field("goal", message("_Goal"), Container::Single),
+                ],
+            ),
+            get_result_response: wrapper(
+                "_GetResult_Response",
+                vec![
+                    field(
+                        "status",
+                        BaseType::Primitive(Primitive::Int8),
+                        Container::Single,
+                    ),
+                    field("result", message("_Result"), Container::Single),
+                ],
+            ),
+            feedback_message: wrapper(
+                "_FeedbackMessage",
+                vec![
+                    goal_id(),
+                    field("feedback", message("_Feedback"), Container::Single),
+                ],
+            ),
+            name,
+            registry,
+        })
+    }
+}
+
+// A goal sent to a ROS 2 action server through the bridge. The feedback and status
+// topics are followed for the lifetime of the goal, and the goal is cancelled on drop
+// unless it already terminated.
+pub struct ActiveGoal {
+    session: zenoh::Session,
+    action: String,
+    goal_id: GoalId,
+    status: Arc<Mutex<GoalStatus>>,
+    _feedback_subscriber: zenoh::pubsub::Subscriber<()>,
+    _status_subscriber: zenoh::pubsub::Subscriber<()>,
+}
+
+impl ActiveGoal {
+    // Sends the goal and returns it together with the stream of its feedback messages
+    pub async fn send<G, F>(
+        session: &zenoh::Session,
+        action: &str,
+        goal: &G,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<F>), capnp::Error>
+    where
+        G: Serialize,
+        F: DeserializeOwned + Send + 'static,
+    {
+        let goal_id = *uuid::Uuid::new_v4().as_bytes();
+        let request = cdr::serialize::<_, _, CdrLe>(&SendGoalRequest { goal_id, goal }, Infinite)
+            .map_err(|e| {
+            capnp::Error::failed(format!("Failed to encode {} goal: {}", action, e))
+        })?;
+
+        let (feedback_tx, feedback_rx) = mpsc::unbounded_channel();
+        let on_feedback =
+            move |payload: &[u8]| match cdr::deserialize::<FeedbackMessage<F>>(payload) {
+                Ok(message) if message.goal_id == goal_id => {
+                    let _ = feedback_tx.send(message.feedback);
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to decode feedback: {}", e),
+            };
+        let goal = Self::send_encoded(session, action, goal_id, request, on_feedback).await?;
+        Ok((goal, feedback_rx))
+    }
+
+    // Sends a goal of an action type from the registry, feedback comes as the type's
+    // `_Feedback` message
+    pub async fn send_dynamic(
+        session: &zenoh::Session,
+        action: &str,
+        types: &DynamicAction,
+        goal: &Value,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<Value>), capnp::Error> {
+        let goal_id = *uuid::Uuid::new_v4().as_bytes();
+        let request = Value::Message(vec![
+            ("goal_id".to_string(), goal_id_value(goal_id)),
+            ("goal".to_string(), goal.clone()),
+        ]);
+        let request = dynamic_cdr::encode_def(&types.registry, &types.send_goal_request, &request)
+            .map_err(|e| capnp::Error::failed(format!("Invalid {} goal: {}", types.name, e)))?;
+
+        let (feedback_tx, feedback_rx) = mpsc::unbounded_channel();
+        let types = types.clone();
+        let on_feedback = move |payload: &[u8]| match dynamic_cdr::decode_def(
+            &types.registry,
+            &types.feedback_message,
+            payload,
+        ) {
+            Ok(message) if message.field("goal_id") == Some(&goal_id_value(goal_id)) => {
+                if let Some(feedback) = message.field("feedback") {
+                    let _ = feedback_tx.send(feedback.clone());
+                }
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to decode {} feedback: {}", types.name, e),
+        };
+        let goal = Self::send_encoded(session, action, goal_id, request, on_feedback).await?;
+        Ok((goal, feedback_rx))
+    }
+
+    // Sends an encoded SendGoal request, `on_feedback` gets every feedback message of the
+    // action, for this goal or any other
+    async fn send_encoded(
+        session: &zenoh::Session,
+        action: &str,
+        goal_id: GoalId,
+        request: Vec<u8>,
+        on_feedback: impl Fn(&[u8]) + Send + Sync + 'static,
+    ) -> Result<Self, capnp::Error> {
+        // Subscribe before sending the goal so early feedback is not missed
+        let feedback_key = format!("{}/_action/feedback", action);
+        let feedback_subscriber = session
+            .declare_subscriber(&feedback_key)
+            .callback(move |sample| on_feedback(&sample.payload().to_bytes()))
+            .await
+            .map_err(|e| {
+                capnp::Error::failed(format!("Failed to subscribe {}: {}", feedback_key, e))
+            })?;
+        let status = Arc::new(Mutex::new(GoalStatus::Unknown));
+        let status_sink = status.clone();
+        let status_key = format!("{}/_action/status", action);
+        let status_subscriber = session
+            .declare_subscriber(&status_key)
+            .callback(move |sample| {
+                match cdr::deserialize::<GoalStatusArray>(&sample.payload().to_bytes()) {
+                    Ok(array) => {
+                        if let Some(entry) = array
+                            .status_list
+                            .iter()
+                            .find(|entry| entry.goal_info.goal_id == goal_id)
+                        {
+                            *status_sink.lock().unwrap() = GoalStatus::from_ros(entry.status);
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to decode goal status: {}", e),
+                }
+            })
+            .await
+            .map_err(|e| {
+                capnp::Error::failed(format!("Failed to subscribe {}: {}", status_key, e))
+            })?;
+
+        let key = format!("{}/_action/send_goal", action);
+        let response = query_service(session, &key, request, SERVICE_TIMEOUT).await?;
+        let response: SendGoalResponse = cdr::deserialize(&response).map_err(|e| {
+            capnp::Error::failed(format!("Failed to decode {} response: {}", key, e))
+        })?;
+        if !response.accepted {
+            return Err(capnp::Error::failed(format!(
+                "Goal rejected by {} action server",
+                action
+            )));
+        }
+        println!("Goal accepted by {} action server", action);
+
+        let mut current = status.lock().unwrap();
+        if *current == GoalStatus::Unknown {
+            *current = GoalStatus::Accepted;
+        }
+        drop(current);
+
+        Ok(Self {
+            session: session.clone(),
+            action: action.to_string(),
+            goal_id,
+            status,
+            _feedback_subscriber: feedback_subscriber,
+            _status_subscriber: status_subscriber,
+        })
+    }
+
+    pub fn status(&self) -> GoalStatus {
+        *self.status.lock().unwrap()
+    }
+
+    // Waits for the goal to terminate and returns its final status and result
+    pub fn result<R>(&self) -> impl Future<Output = Result<(GoalStatus, R), capnp::Error>> + 'static
+    where
+        R: DeserializeOwned,
+    {
+        self.result_with(|payload| {
+            let response: GetResultResponse<R> = cdr::deserialize(payload)
+                .map_err(|e| capnp::Error::failed(format!("Failed to decode result: {}", e)))?;
+            Ok((response.status, response.result))
+        })
+    }
+
+    // Same as `result` for an action type from the registry, as its `_Result` message
+    pub fn result_dynamic(
+        &self,
+        types: &DynamicAction,
+    ) -> impl Future<Output = Result<(GoalStatus, Value), capnp::Error>> + 'static {
+        let types = types.clone();
+        self.result_with(move |payload| {
+            let response =
+                dynamic_cdr::decode_def(&types.registry, &types.get_result_response, payload)
+                    .map_err(|e| {
+                        capnp::Error::failed(format!("Invalid {} result: {}", types.name, e))
+                    })?;
+            match (response.field("status"), response.field("result")) {
+                (Some(Value::Int8(status)), Some(result)) => Ok((*status, result.clone())),
+                _ => Err(capnp::Error::failed(format!(
+                    "Invalid {} result",
+                    types.name
+                ))),
+            }
+        })
+    }
+
+    // Queries get_result and decodes the response into its status and result
+    fn result_with<R>(
+        &self,
+        decode: impl FnOnce(&[u8]) -> Result<(i8, R), capnp::Error> + 'static,
+    ) -> impl Future<Output = Result<(GoalStatus, R), capnp::Error>> + 'static {
+        let session = self.session.clone();
+        let key = format!("{}/_action/get_result", self.action);
+        let request = cdr::serialize::<_, _, CdrLe>(
+            &GetResultRequest {
+                goal_id: self.goal_id,
+            },
+            Infinite,
+        );
+        let status = self.status.clone();
+        async move {
+            let request = request.map_err(|e| {
+                capnp::Error::failed(format!("Failed to encode {} request: {}", key, e))
+            })?;
+            let response = query_service(&session, &key, request, RESULT_TIMEOUT).await?;
+            let (ros_status, result) = decode(&response)?;
+            let final_status = GoalStatus::from_ros(ros_status);
+            *status.lock().unwrap() = final_status;
+            Ok((final_status, result))
+        }
+    }
+
+    pub fn cancel(&self) -> impl Future<Output = Result<(), capnp::Error>> + 'static {
+        cancel_goal(self.session.clone(), self.action.clone(), self.goal_id)
+    }
+}
+
+async fn cancel_goal(
+    session: zenoh::Session,
+    action: String,
+    goal_id: GoalId,
+) -> Result<(), capnp::Error> {
+    let request = CancelGoalRequest {
+        goal_info: GoalInfo {
+            goal_id,
+            stamp: Time::default(),
+        },
+    };
+    let key = format!("{}/_action/cancel_goal", action);
+    let response: CancelGoalResponse = call_service(&session, &key, &request).await?;
+    // action_msgs/srv/CancelGoal return codes: 0 none, 1 rejected, 2 unknown goal, 3 terminated
+    match response.return_code {
+        0 => Ok(()),
+        code => Err(capnp::Error::failed(format!(
+            "Cancel of goal on {} refused with code {}",
+            action, code
+        ))),
+    }
+}
+
+impl Drop for ActiveGoal {
+    fn drop(&mut self) {
+        if self.status().is_terminal() {
+            return;
+        }
+        println!("Goal handle on {} dropped, cancelling goal", self.action);
+        let cancel = cancel_goal(self.session.clone(), self.action.clone(), self.goal_id);
+        tokio::spawn(async move {
+            if let Err(e) = cancel.await {
+                eprintln!("Failed to cancel dropped goal: {}", e);
+            }
+        });
+    }
+}
+
+// Goal handle of a goal sent through Bootstrap.sendGoal, cancels the goal when dropped
+pub struct GenericGoalHandle {
+    pub goal: ActiveGoal,
+    pub types: DynamicAction,
+}
+
+impl GenericGoalHandle {
+    // Forwards feedback to the client callback until the goal is dropped
+    pub fn forward_feedback(
+        mut feedback: mpsc::UnboundedReceiver<Value>,
+        callback: goal_feedback::Client,
+    ) {
+        tokio::task::spawn_local(async move {
+            while let Some(message) = feedback.recv().await {
+                let mut request = callback.on_feedback_request();
+                write_value(&message, request.get().init_feedback());
+                if let Err(e) = request.send().promise.await {
+                    eprintln!("Failed to deliver feedback to client: {}", e);
+                    break;
+                }
+            }
+        });
+    }
+}
+
+impl goal_handle::Server for GenericGoalHandle {
+    fn get_status(
+        &mut self,
+        _: goal_handle::GetStatusParams,
+        mut results: goal_handle::GetStatusResults,
+    ) -> Promise<(), capnp::Error> {
+        results.get().set_status(self.goal.status().into());
+        Promise::ok(())
+    }
+
+    fn cancel(
+        &mut self,
+        _: goal_handle::CancelParams,
+        _: goal_handle::CancelResults,
+    ) -> Promise<(), capnp::Error> {
+        Promise::from_future(self.goal.cancel())
+    }
+}
+
+impl generic_goal_handle::Server for GenericGoalHandle {
+    fn get_result(
+        &mut self,
+        _: generic_goal_handle::GetResultParams,
+        mut results: generic_goal_handle::GetResultResults,
+    ) -> Promise<(), capnp::Error> {
+        let result = self.goal.result_dynamic(&self.types);
+        Promise::from_future(async move {
+            let (status, result) = result.await?;
+            let mut results = results.get();
+            results.set_status(status.into());
+            write_value(&result, results.init_result());
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::ros_types::{builtin_interfaces, rosgraph_msgs};
+use crate::sink::{Encoding, RouterPublisher};
+use crate::tf::{from_nanos, to_nanos};
+
+// How often a published clock is sent, in wall time
+const PUBLISH_PERIOD: Duration = Duration::from_millis(10);
+
+// Which time the router stamps its messages with, chosen per deployment
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ClockSource {
+    #[default]
+    Wall,
+    // ROS sim time received on /clock
+    Sim,
+    // Sim time published by the router on /clock, starting at the wall time and advancing
+    // `rate` times as fast, for replaying recordings
+    Publish {
+        rate: f64,
+    },
+}
+
+enum Source {
+    Wall,
+    // Latest time received on /clock, zero until the first message as in ROS
+    Sim {
+        now: Arc<AtomicI64>,
+        _subscriber: zenoh::pubsub::Subscriber<()>,
+    },
+    Publish {
+        start: i64,
+        origin: Instant,
+        rate: f64,
+    },
+}
+
+// Shared by everything that generates timestamps or measures timeouts in ROS time, such as
+// dead reckoning. Zenoh query timeouts stay on wall time, see query::SERVICE_TIMEOUT
+#[derive(Clone)]
+pub struct Clock(Arc<Source>);
+
+fn wall_nanos() -> i64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_nanos() as i64
+}
+
+impl Clock {
+    pub fn wall() -> Self {
+        Self(Arc::new(Source::Wall))
+    }
+
+    pub async fn start(
+        session: &zenoh::Session,
+        source: ClockSource,
+        publisher: RouterPublisher,
+    ) -> Result<Self, capnp::Error> {
+        match source {
+            ClockSource::Wall => Ok(Self::wall()),
+            ClockSource::Sim => Self::subscribe(session).await,
+            ClockSource::Publish { rate } => Self::publish(publisher, rate),
+        }
+    }
+
+    async fn subscribe(session: &zenoh::Session) -> Result<Self, capnp::Error> {
+        let now = Arc::new(AtomicI64::new(0));
+        let latest = now.clone();
+        let subscriber = session
+            .declare_subscriber("clock")
+            .callback(move |sample| {
+                match cdr::deserialize::<rosgraph_msgs::Clock>(&sample.payload().to_bytes()) {
+                    Ok(clock) => latest.store(to_nanos(&clock.clock), Ordering::Relaxed),
+                    Err(e) => eprintln!("Failed to decode clock: {}", e),
+                }
+            })
+            .await
+            .map_err(|e| capnp::Error::failed(format!("Failed to subscribe clock: {}", e)))?;
+        Ok(Self(Arc::new(Source::Sim {
+            now,
+            _subscriber: subscriber,
+        })))
+    }
+
+    fn publish(publisher: RouterPublisher, rate: f64) -> Result<Self, capnp::Error> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(capnp::Error::failed(format!("Invalid clock rate {}", rate)));
+        }
+        let clock = Self(Arc::new(Source::Publish {
+            start: wall_nanos(),
+            origin: Instant::now(),
+            rate,
+        }));
+
+        // Stops once every copy of the clock is dropped
+        let weak = Arc::downgrade(&clock.0);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PUBLISH_PERIOD);
+            loop {
+                interval.tick().await;
+                let Some(source) = weak.upgrade() else {
+                    break;
+                };
+                let message = rosgraph_msgs::Clock {
+                    clock: Clock(source).stamp(),
+                };
+                let encoded = cdr::serialize::<_, _, cdr::CdrLe>(&message, cdr::Infinite).unwrap();
+                let published =
+                    publisher.publish("clock", encoded, Encoding::cdr("rosgraph_msgs/msg/Clock"));
+                if let Err(e) = published.await {
+                    eprintln!("Failed to publish clock: {}", e);
+                }
+            }
+        });
+        Ok(clock)
+    }
+
+    // Current time in nanoseconds since the epoch of the source
+    pub fn now(&self) -> i64 {
+        match &*self.0 {
+            Source::Wall => wall_nanos(),
+            Source::Sim { now, .. } => now.load(Ordering::Relaxed),
+            Source::Publish {
+                start,
+                origin,
+                rate,
+            } => start + (origin.elapsed().as_nanos() as f64 * rate) as i64,
+        }
+    }
+
+    pub fn stamp(&self) -> builtin_interfaces::Time {
+        from_nanos(self.now())
+    }
+}
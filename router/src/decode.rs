@@ -0,0 +1,50 @@
+// Subscriber side of the encodings the router puts, payloads are decoded according to the
+// encoding they arrive with
+
+use capnp::message::ReaderOptions;
+use capnp::serialize::OwnedSegments;
+use capnp::serialize_packed;
+use zenoh::sample::Sample;
+
+use crate::dynamic_cdr;
+pub use crate::dynamic_cdr::Value;
+use crate::ros_idl::TypeRegistry;
+use crate::sink::Encoding;
+
+pub enum Decoded {
+    Ros { ros_type: String, value: Value },
+    Capnp(capnp::message::Reader<OwnedSegments>),
+    Json(serde_json::Value),
+    Text(String),
+}
+
+// ROS types are looked up in `registry`
+pub fn decode(
+    encoding: &zenoh::bytes::Encoding,
+    payload: &[u8],
+    registry: &TypeRegistry,
+) -> Result<Decoded, String> {
+    match Encoding::from_zenoh(encoding) {
+        Some(Encoding::Cdr(ros_type)) => {
+            let value = dynamic_cdr::decode(registry, &ros_type, payload)
+                .map_err(|e| format!("Invalid {}: {}", ros_type, e))?;
+            Ok(Decoded::Ros { ros_type, value })
+        }
+        Some(Encoding::CapnpPacked) => {
+            serialize_packed::read_message(payload, ReaderOptions::new())
+                .map(Decoded::Capnp)
+                .map_err(|e| format!("Invalid Cap'n Proto message: {}", e))
+        }
+        Some(Encoding::Json) => serde_json::from_slice(payload)
+            .map(Decoded::Json)
+            .map_err(|e| format!("Invalid JSON: {}", e)),
+        Some(Encoding::Text) => String::from_utf8(payload.to_vec())
+            .map(Decoded::Text)
+            .map_err(|e| format!("Invalid text: {}", e)),
+        None => Err(format!("Unsupported encoding {}", encoding)),
+    }
+}
+
+pub fn decode_sample(sample: &Sample, registry: &TypeRegistry) -> Result<Decoded, String> {
+    decode(sample.encoding(), &sample.payload().to_bytes(), registry)
+}
@@ -0,0 +1,99 @@
+// Payloads decoded by the encoding they are labelled with
+
+use std::path::Path;
+
+use cdr::{CdrLe, Infinite};
+
+use crate::decode::{Decoded, Value, decode};
+use crate::ros_idl::TypeRegistry;
+use crate::ros_types::geometry_msgs;
+use crate::schema_capnp::hello;
+use crate::sink::Encoding;
+
+fn registry() -> TypeRegistry {
+    TypeRegistry::load_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../msgs")).unwrap()
+}
+
+#[test]
+fn encodings_survive_zenoh() {
+    for encoding in [
+        Encoding::cdr("geometry_msgs/msg/Twist"),
+        Encoding::CapnpPacked,
+        Encoding::Json,
+        Encoding::Text,
+    ] {
+        let zenoh_encoding = zenoh::bytes::Encoding::from(encoding.clone());
+        assert_eq!(Encoding::from_zenoh(&zenoh_encoding), Some(encoding));
+    }
+    assert_eq!(
+        Encoding::cdr("geometry_msgs/msg/Twist").to_string(),
+        "application/cdr;geometry_msgs/msg/Twist"
+    );
+    // CDR without a type cannot be decoded generically
+    assert_eq!(
+        Encoding::from_zenoh(&zenoh::bytes::Encoding::APPLICATION_CDR),
+        None
+    );
+}
+
+#[test]
+fn cdr_is_decoded_with_its_ros_type() {
+    let twist = geometry_msgs::Twist {
+        linear: geometry_msgs::Vector3 {
+            x: 1.5,
+            y: 0.0,
+            z: 0.0,
+        },
+        angular: geometry_msgs::Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: -0.5,
+        },
+    };
+    let payload = cdr::serialize::<_, _, CdrLe>(&twist, Infinite).unwrap();
+    let encoding = Encoding::cdr("geometry_msgs/msg/Twist").into();
+
+    let Ok(Decoded::Ros { ros_type, value }) = decode(&encoding, &payload, &registry()) else {
+        panic!("Twist not decoded as ROS");
+    };
+    assert_eq!(ros_type, "geometry_msgs/msg/Twist");
+    let linear = value.field("linear").unwrap();
+    assert_eq!(linear.field("x"), Some(&Value::Float64(1.5)));
+    let angular = value.field("angular").unwrap();
+    assert_eq!(angular.field("z"), Some(&Value::Float64(-0.5)));
+}
+
+#[test]
+fn packed_capnp_json_and_text_are_decoded() {
+    let mut message = capnp::message::Builder::new_default();
+    message.init_root::<hello::Builder>().set_msg("hi");
+    let mut payload = Vec::new();
+    capnp::serialize_packed::write_message(&mut payload, &message).unwrap();
+    let Ok(Decoded::Capnp(reader)) = decode(&Encoding::CapnpPacked.into(), &payload, &registry())
+    else {
+        panic!("Hello not decoded as Cap'n Proto");
+    };
+    let hello = reader.get_root::<hello::Reader>().unwrap();
+    assert_eq!(hello.get_msg().unwrap(), "hi");
+
+    let Ok(Decoded::Json(json)) = decode(&Encoding::Json.into(), br#"{"a": 1}"#, &registry())
+    else {
+        panic!("JSON not decoded");
+    };
+    assert_eq!(json["a"], 1);
+
+    let Ok(Decoded::Text(text)) = decode(&Encoding::Text.into(), b"hello", &registry()) else {
+        panic!("Text not decoded");
+    };
+    assert_eq!(text, "hello");
+}
+
+#[test]
+fn unknown_encodings_are_rejected() {
+    let result = decode(
+        &zenoh::bytes::Encoding::IMAGE_PNG,
+        &[0x89, 0x50],
+        &registry(),
+    );
+    assert!(matches!(result, Err(e) if e.contains("Unsupported encoding")));
+}
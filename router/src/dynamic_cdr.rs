@@ -0,0 +1,554 @@
+use std::fmt;
+
+use crate::ros_idl::{BaseType, Container, FieldDef, MessageDef, Primitive, TypeRegistry};
+
+// CDR encapsulation headers, the router always writes little-endian
+const CDR_BE: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+const CDR_LE: [u8; 4] = [0x00, 0x01, 0x00, 0x00];
+
+// A ROS message whose layout is only known at runtime from a `MessageDef`
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    Bool(bool),
+    Int8(i8),
+    UInt8(u8),
+    Int16(i16),
+    UInt16(u16),
+    Int32(i32),
+    UInt32(u32),
+    Int64(i64),
+    UInt64(u64),
+    Float32(f32),
+    Float64(f64),
+    String(String),
+    // Fixed size arrays and sequences
+    Array(Vec<Value>),
+    // Fields in declaration order
+    Message(Vec<(String, Value)>),
+}
+
+impl Value {
+    // Looks up a field of a message value
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Self::Message(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    // Numeric primitives widened to f64
+    pub fn as_f64(&self) -> Option<f64> {
+        Some(match *self {
+            Self::Int8(v) => v as f64,
+            Self::UInt8(v) => v as f64,
+            Self::Int16(v) => v as f64,
+            Self::UInt16(v) => v as f64,
+            Self::Int32(v) => v as f64,
+            Self::UInt32(v) => v as f64,
+            Self::Int64(v) => v as f64,
+            Self::UInt64(v) => v as f64,
+            Self::Float32(v) => v as f64,
+            Self::Float64(v) => v,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CodecError(pub String);
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+fn lookup<'a>(registry: &'a TypeRegistry, name: &str) -> Result<&'a MessageDef, CodecError> {
+    registry
+        .get(name)
+        .ok_or_else(|| CodecError(format!("Unknown message type {}", name)))
+}
+
+// Parses a literal from a .msg default or constant into a value of the given primitive
+pub fn parse_literal(primitive: Primitive, text: &str) -> Result<Value, CodecError> {
+    let error = || CodecError(format!("Invalid {:?} literal {}", primitive, text));
+    let text = text.trim();
+    Ok(match primitive {
+        Primitive::Bool => match text {
+            "true" | "True" | "1" => Value::Bool(true),
+            "false" | "False" | "0" => Value::Bool(false),
+            _ => return Err(error()),
+        },
+        Primitive::Byte | Primitive::Char | Primitive::UInt8 => {
+            Value::UInt8(text.parse().map_err(|_| error())?)
+        }
+        Primitive::Int8 => Value::Int8(text.parse().map_err(|_| error())?),
+        Primitive::Int16 => Value::Int16(text.parse().map_err(|_| error())?),
+        Primitive::UInt16 => Value::UInt16(text.parse().map_err(|_| error())?),
+        Primitive::Int32 => Value::Int32(text.parse().map_err(|_| error())?),
+        Primitive::UInt32 => Value::UInt32(text.parse().map_err(|_| error())?),
+        Primitive::Int64 => Value::Int64(text.parse().map_err(|_| error())?),
+        Primitive::UInt64 => Value::UInt64(text.parse().map_err(|_| error())?),
+        Primitive::Float32 => Value::Float32(text.parse().map_err(|_| error())?),
+        Primitive::Float64 => Value::Float64(text.parse().map_err(|_| error())?),
+        Primitive::String => {
+            let unquoted = text
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .or_else(|| text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')))
+                .unwrap_or(text);
+            Value::String(unquoted.to_string())
+        }
+    })
+}
+
+fn primitive_of(base: &BaseType) -> Option<Primitive> {
+    match base {
+        BaseType::Primitive(primitive) => Some(*primitive),
+        BaseType::BoundedString(_) => Some(Primitive::String),
+        BaseType::Message(_) => None,
+    }
+}
+
+// Splits an array default such as `[1, 2, 3]` into its elements
+fn split_array_literal(text: &str) -> Result<Vec<&str>, CodecError> {
+    let inner = text
+        .trim()
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .ok_or_else(|| CodecError(format!("Invalid array literal {}", text)))?
+        .trim();
+    if inner.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(inner.split(',').map(str::trim).collect())
+}
+
+fn default_base(registry: &TypeRegistry, base: &BaseType) -> Result<Value, CodecError> {
+    match base {
+        BaseType::Message(name) => default_value(registry, name),
+        _ => Ok(match primitive_of(base).unwrap() {
+            Primitive::Bool => Value::Bool(false),
+            Primitive::Byte | Primitive::Char | Primitive::UInt8 => Value::UInt8(0),
+            Primitive::Int8 => Value::Int8(0),
+            Primitive::Int16 => Value::Int16(0),
+            Primitive::UInt16 => Value::UInt16(0),
+            Primitive::Int32 => Value::Int32(0),
+            Primitive::UInt32 => Value::UInt32(0),
+            Primitive::Int64 => Value::Int64(0),
+            Primitive::UInt64 => Value::UInt64(0),
+            Primitive::Float32 => Value::Float32(0.0),
+            Primitive::Float64 => Value::Float64(0.0),
+            Primitive::String => Value::String(String::new()),
+        }),
+    }
+}
+
+fn default_field(registry: &TypeRegistry, field: &FieldDef) -> Result<Value, CodecError> {
+    if let (Some(default), Some(primitive)) = (&field.default, primitive_of(&field.base)) {
+        return match field.container {
+            Container::Single => parse_literal(primitive, default),
+            _ => Ok(Value::Array(
+                split_array_literal(default)?
+                    .into_iter()
+                    .map(|element| parse_literal(primitive, element))
+                    .collect::<Result<_, _>>()?,
+            )),
+        };
+    }
+    match field.container {
+        Container::Single => default_base(registry, &field.base),
+        Container::Array(size) => Ok(Value::Array(
+            (0..size)
+                .map(|_| default_base(registry, &field.base))
+                .collect::<Result<_, _>>()?,
+        )),
+        Container::Sequence(_) => Ok(Value::Array(Vec::new())),
+    }
+}
+
+// Builds a message with every field set to its declared default, or zero
+pub fn default_value(registry: &TypeRegistry, type_name: &str) -> Result<Value, CodecError> {
+    let def = lookup(registry, type_name)?;
+    Ok(Value::Message(
+        def.fields
+            .iter()
+            .map(|field| Ok((field.name.clone(), default_field(registry, field)?)))
+            .collect::<Result<_, CodecError>>()?,
+    ))
+}
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    // Alignment is relative to the end of the encapsulation header
+    fn align(&mut self, alignment: usize) {
+        while !(self.buf.len() - CDR_LE.len()).is_multiple_of(alignment) {
+            self.buf.push(0);
+        }
+    }
+
+    fn write(&mut self, alignment: usize, bytes: &[u8]) {
+        self.align(alignment);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn write_length(&mut self, length: usize) {
+        self.write(4, &(length as u32).to_le_bytes());
+    }
+
+    fn primitive(&mut self, primitive: Primitive, value: &Value) -> Result<(), CodecError> {
+        match (primitive, value) {
+            (Primitive::Bool, Value::Bool(v)) => self.write(1, &[*v as u8]),
+            (Primitive::Byte | Primitive::Char | Primitive::UInt8, Value::UInt8(v)) => {
+                self.write(1, &[*v])
+            }
+            (Primitive::Int8, Value::Int8(v)) => self.write(1, &v.to_le_bytes()),
+            (Primitive::Int16, Value::Int16(v)) => self.write(2, &v.to_le_bytes()),
+            (Primitive::UInt16, Value::UInt16(v)) => self.write(2, &v.to_le_bytes()),
+            (Primitive::Int32, Value::Int32(v)) => self.write(4, &v.to_le_bytes()),
+            (Primitive::UInt32, Value::UInt32(v)) => self.write(4, &v.to_le_bytes()),
+            (Primitive::Int64, Value::Int64(v)) => self.write(8, &v.to_le_bytes()),
+            (Primitive::UInt64, Value::UInt64(v)) => self.write(8, &v.to_le_bytes()),
+            (Primitive::Float32, Value::Float32(v)) => self.write(4, &v.to_le_bytes()),
+            (Primitive::Float64, Value::Float64(v)) => self.write(8, &v.to_le_bytes()),
+            (Primitive::String, Value::String(v)) => {
+                // The length includes the terminating NUL
+                self.write_length(v.len() + 1);
+                self.buf.extend_from_slice(v.as_bytes());
+                self.buf.push(0);
+            }
+            _ => {
+                return Err(CodecError(format!(
+                    "Expected a {:?} value, got {:?}",
+                    primitive, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn base(
+        &mut self,
+        registry: &TypeRegistry,
+        base: &BaseType,
+        value: &Value,
+    ) -> Result<(), CodecError> {
+        match base {
+            BaseType::Primitive(primitive) => self.primitive(*primitive, value),
+            BaseType::BoundedString(bound) => match value {
+                Value::String(s) if s.len() > *bound => Err(CodecError(format!(
+                    "String of length {} exceeds bound {}",
+                    s.len(),
+                    bound
+                ))),
+                _ => self.primitive(Primitive::String, value),
+            },
+            BaseType::Message(name) => self.message(registry, lookup(registry, name)?, value),
+        }
+    }
+
+    fn field(
+        &mut self,
+        registry: &TypeRegistry,
+        field: &FieldDef,
+        value: &Value,
+    ) -> Result<(), CodecError> {
+        let elements = match (field.container, value) {
+            (Container::Single, _) => return self.base(registry, &field.base, value),
+            (_, Value::Array(elements)) => elements,
+            _ => {
+                return Err(CodecError(format!(
+                    "Field {} expects an array, got {:?}",
+                    field.name, value
+                )));
+            }
+        };
+        match field.container {
+            Container::Array(size) if elements.len() != size => {
+                return Err(CodecError(format!(
+                    "Field {} expects {} elements, got {}",
+                    field.name,
+                    size,
+                    elements.len()
+                )));
+            }
+            Container::Sequence(Some(max)) if elements.len() > max => {
+                return Err(CodecError(format!(
+                    "Field {} holds at most {} elements, got {}",
+                    field.name,
+                    max,
+                    elements.len()
+                )));
+            }
+            Container::Sequence(_) => self.write_length(elements.len()),
+            _ => {}
+        }
+        for element in elements {
+            self.base(registry, &field.base, element)?;
+        }
+        Ok(())
+    }
+
+    fn message(
+        &mut self,
+        registry: &TypeRegistry,
+        def: &MessageDef,
+        value: &Value,
+    ) -> Result<(), CodecError> {
+        let Value::Message(fields) = value else {
+            return Err(CodecError(format!(
+                "Expected a {} message, got {:?}",
+                def.name, value
+            )));
+        };
+        if let Some((unknown, _)) = fields
+            .iter()
+            .find(|(name, _)| !def.fields.iter().any(|f| &f.name == name))
+        {
+            return Err(CodecError(format!("{} has no field {}", def.name, unknown)));
+        }
+        if def.fields.is_empty() {
+            // Empty messages carry a single placeholder byte
+            self.write(1, &[0]);
+            return Ok(());
+        }
+        for field in &def.fields {
+            match value.field(&field.name) {
+                Some(v) => self.field(registry, field, v)?,
+                // Missing fields take their default value
+                None => self.field(registry, field, &default_field(registry, field)?)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Encodes a message as XCDR1 little-endian, including the encapsulation header
+pub fn encode(
+    registry: &TypeRegistry,
+    type_name: &str,
+    value: &Value,
+) -> Result<Vec<u8>, CodecError> {
+    encode_def(registry, lookup(registry, type_name)?, value)
+}
+
+// Same as `encode` for a definition that is not in the registry, its nested types must be
+pub fn encode_def(
+    registry: &TypeRegistry,
+    def: &MessageDef,
+    value: &Value,
+) -> Result<Vec<u8>, CodecError> {
+    let mut writer = Writer {
+        buf: CDR_LE.to_vec(),
+    };
+    writer.message(registry, def, value)?;
+    Ok(writer.buf)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, alignment: usize, size: usize) -> Result<&'a [u8], CodecError> {
+        // Alignment is relative to the end of the encapsulation header
+        let offset = self.pos - CDR_LE.len();
+        self.pos += (alignment - offset % alignment) % alignment;
+        let end = self.pos + size;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or_else(|| CodecError(format!("Payload truncated at offset {}", self.pos)))?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn fixed<const N: usize>(&mut self) -> Result<[u8; N], CodecError> {
+        let mut bytes: [u8; N] = self.take(N, N)?.try_into().unwrap();
+        if !self.little_endian {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn length(&mut self) -> Result<usize, CodecError> {
+        Ok(u32::from_le_bytes(self.fixed()?) as usize)
+    }
+
+    fn primitive(&mut self, primitive: Primitive) -> Result<Value, CodecError> {
+        Ok(match primitive {
+            Primitive::Bool => Value::Bool(self.take(1, 1)?[0] != 0),
+            Primitive::Byte | Primitive::Char | Primitive::UInt8 => {
+                Value::UInt8(self.take(1, 1)?[0])
+            }
+            Primitive::Int8 => Value::Int8(self.take(1, 1)?[0] as i8),
+            Primitive::Int16 => Value::Int16(i16::from_le_bytes(self.fixed()?)),
+            Primitive::UInt16 => Value::UInt16(u16::from_le_bytes(self.fixed()?)),
+            Primitive::Int32 => Value::Int32(i32::from_le_bytes(self.fixed()?)),
+            Primitive::UInt32 => Value::UInt32(u32::from_le_bytes(self.fixed()?)),
+            Primitive::Int64 => Value::Int64(i64::from_le_bytes(self.fixed()?)),
+            Primitive::UInt64 => Value::UInt64(u64::from_le_bytes(self.fixed()?)),
+            Primitive::Float32 => Value::Float32(f32::from_le_bytes(self.fixed()?)),
+            Primitive::Float64 => Value::Float64(f64::from_le_bytes(self.fixed()?)),
+            Primitive::String => {
+                let length = self.length()?;
+                let bytes = self.take(1, length)?;
+                // Drop the terminating NUL
+                let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+                Value::String(
+                    String::from_utf8(bytes.to_vec())
+                        .map_err(|e| CodecError(format!("Invalid UTF-8 in string: {}", e)))?,
+                )
+            }
+        })
+    }
+
+    fn base(&mut self, registry: &TypeRegistry, base: &BaseType) -> Result<Value, CodecError> {
+        match base {
+            BaseType::Primitive(primitive) => self.primitive(*primitive),
+            BaseType::BoundedString(_) => self.primitive(Primitive::String),
+            BaseType::Message(name) => self.message(registry, lookup(registry, name)?),
+        }
+    }
+
+    fn field(&mut self, registry: &TypeRegistry, field: &FieldDef) -> Result<Value, CodecError> {
+        let count = match field.container {
+            Container::Single => return self.base(registry, &field.base),
+            Container::Array(size) => size,
+            Container::Sequence(_) => self.length()?,
+        };
+        // Guard against corrupt lengths before allocating
+        if count > self.data.len() {
+            return Err(CodecError(format!(
+                "Field {} claims {} elements in a {} byte payload",
+                field.name,
+                count,
+                self.data.len()
+            )));
+        }
+        let mut elements = Vec::with_capacity(count);
+        for _ in 0..count {
+            elements.push(self.base(registry, &field.base)?);
+        }
+        Ok(Value::Array(elements))
+    }
+
+    fn message(&mut self, registry: &TypeRegistry, def: &MessageDef) -> Result<Value, CodecError> {
+        if def.fields.is_empty() {
+            self.take(1, 1)?;
+            return Ok(Value::Message(Vec::new()));
+        }
+        let mut fields = Vec::with_capacity(def.fields.len());
+        for field in &def.fields {
+            fields.push((field.name.clone(), self.field(registry, field)?));
+        }
+        Ok(Value::Message(fields))
+    }
+}
+
+// Decodes a CDR payload of either endianness, including the encapsulation header
+pub fn decode(registry: &TypeRegistry, type_name: &str, data: &[u8]) -> Result<Value, CodecError> {
+    decode_def(registry, lookup(registry, type_name)?, data)
+}
+
+// Same as `decode` for a definition that is not in the registry, its nested types must be
+pub fn decode_def(
+    registry: &TypeRegistry,
+    def: &MessageDef,
+    data: &[u8],
+) -> Result<Value, CodecError> {
+    // The last two header bytes are options and ignored
+    let little_endian = match data.get(..CDR_LE.len()) {
+        Some(header) if header[..2] == CDR_LE[..2] => true,
+        Some(header) if header[..2] == CDR_BE[..2] => false,
+        Some(header) => {
+            return Err(CodecError(format!(
+                "Unsupported CDR encapsulation {:?}",
+                header
+            )));
+        }
+        None => return Err(CodecError("Payload shorter than CDR header".to_string())),
+    };
+    let mut reader = Reader {
+        data,
+        pos: CDR_LE.len(),
+        little_endian,
+    };
+    reader.message(registry, def)
+}
+
+fn json_primitive(primitive: Primitive, json: &serde_json::Value) -> Result<Value, CodecError> {
+    let error = || CodecError(format!("Expected a {:?} value, got {}", primitive, json));
+    let int = || json.as_i64().ok_or_else(error);
+    let uint = || json.as_u64().ok_or_else(error);
+    Ok(match primitive {
+        Primitive::Bool => Value::Bool(json.as_bool().ok_or_else(error)?),
+        Primitive::Byte | Primitive::Char | Primitive::UInt8 => {
+            Value::UInt8(uint()?.try_into().map_err(|_| error())?)
+        }
+        Primitive::Int8 => Value::Int8(int()?.try_into().map_err(|_| error())?),
+        Primitive::Int16 => Value::Int16(int()?.try_into().map_err(|_| error())?),
+        Primitive::UInt16 => Value::UInt16(uint()?.try_into().map_err(|_| error())?),
+        Primitive::Int32 => Value::Int32(int()?.try_into().map_err(|_| error())?),
+        Primitive::UInt32 => Value::UInt32(uint()?.try_into().map_err(|_| error())?),
+        Primitive::Int64 => Value::Int64(int()?),
+        Primitive::UInt64 => Value::UInt64(uint()?),
+        Primitive::Float32 => Value::Float32(json.as_f64().ok_or_else(error)? as f32),
+        Primitive::Float64 => Value::Float64(json.as_f64().ok_or_else(error)?),
+        Primitive::String => Value::String(json.as_str().ok_or_else(error)?.to_string()),
+    })
+}
+
+fn json_base(
+    registry: &TypeRegistry,
+    base: &BaseType,
+    json: &serde_json::Value,
+) -> Result<Value, CodecError> {
+    match base {
+        BaseType::Message(name) => from_json(registry, name, json),
+        _ => json_primitive(primitive_of(base).unwrap(), json),
+    }
+}
+
+// Converts JSON into a message value, using the message definition to pick the
+// numeric types. Missing fields are left out and take their default when encoded.
+pub fn from_json(
+    registry: &TypeRegistry,
+    type_name: &str,
+    json: &serde_json::Value,
+) -> Result<Value, CodecError> {
+    let def = lookup(registry, type_name)?;
+    let object = json
+        .as_object()
+        .ok_or_else(|| CodecError(format!("Expected a {} object, got {}", def.name, json)))?;
+
+    let mut fields = Vec::with_capacity(object.len());
+    for (name, json) in object {
+        let field = def
+            .fields
+            .iter()
+            .find(|f| &f.name == name)
+            .ok_or_else(|| CodecError(format!("{} has no field {}", def.name, name)))?;
+        let value = match field.container {
+            Container::Single => json_base(registry, &field.base, json)?,
+            _ => Value::Array(
+                json.as_array()
+                    .ok_or_else(|| {
+                        CodecError(format!("Field {} expects an array, got {}", name, json))
+                    })?
+                    .iter()
+                    .map(|element| json_base(registry, &field.base, element))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        fields.push((name.clone(), value));
+    }
+    Ok(Value::Message(fields))
+}
@@ -0,0 +1,312 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use capnp::capability::Promise;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{ColorType, DynamicImage, ImageBuffer, ImageReader};
+use tokio::sync::watch;
+
+use crate::messages::min_interval;
+use crate::ros_types::{sensor_msgs, std_msgs};
+use crate::schema_capnp::{
+    ImageFormat, image_frame, image_listener, image_stream_options, subscription,
+};
+
+const DEFAULT_JPEG_QUALITY: u8 = 75;
+
+pub struct ImageStreamOptions {
+    pub format: ImageFormat,
+    // Zero when the client did not ask for a specific quality
+    pub quality: u8,
+    pub max_width: u32,
+    pub max_height: u32,
+    pub min_interval: Option<Duration>,
+}
+
+impl ImageStreamOptions {
+    pub fn read(reader: image_stream_options::Reader) -> Result<Self, capnp::Error> {
+        let quality = reader.get_quality();
+        if quality > 100 {
+            return Err(capnp::Error::failed(format!("Invalid quality {}", quality)));
+        }
+        let min_interval = min_interval(reader.get_max_rate())?;
+        Ok(Self {
+            format: reader.get_format()?,
+            quality,
+            max_width: reader.get_max_width(),
+            max_height: reader.get_max_height(),
+            min_interval,
+        })
+    }
+
+    fn fits(&self, width: u32, height: u32) -> bool {
+        (self.max_width == 0 || width <= self.max_width)
+            && (self.max_height == 0 || height <= self.max_height)
+    }
+}
+
+// A frame encoded for one subscriber
+pub struct Frame {
+    pub header: std_msgs::Header,
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    fn write(&self, mut builder: image_frame::Builder) {
+        self.header.write(builder.reborrow().init_header());
+        builder.set_format(self.format);
+        builder.set_width(self.width);
+        builder.set_height(self.height);
+        builder.set_data(&self.data);
+    }
+}
+
+// Copies the pixels of a sensor_msgs/Image, dropping the padding at the end of each row
+pub fn decode_raw(image: &sensor_msgs::Image) -> Result<DynamicImage, String> {
+    let pixel_size = match image.encoding.as_str() {
+        "rgb8" | "bgr8" => 3,
+        "rgba8" | "bgra8" => 4,
+        "mono8" | "8UC1" => 1,
+        "mono16" | "16UC1" => 2,
+        other => return Err(format!("Unsupported image encoding {}", other)),
+    };
+    let (width, height) = (image.width, image.height);
+    let row = width as usize * pixel_size;
+    let step = image.step as usize;
+    if row == 0 || height == 0 {
+        return Err("Image is empty".to_string());
+    }
+    if step < row || image.data.len() < step * height as usize {
+        return Err(format!(
+            "Image data of {} bytes is too short for {}x{} {} with step {}",
+            image.data.len(),
+            width,
+            height,
+            image.encoding,
+            step
+        ));
+    }
+
+    let mut pixels = Vec::with_capacity(row * height as usize);
+    for line in image.data.chunks(step).take(height as usize) {
+        pixels.extend_from_slice(&line[..row]);
+    }
+    let decoded = match image.encoding.as_str() {
+        "rgb8" => ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8),
+        "bgr8" => {
+            pixels.chunks_exact_mut(3).for_each(|px| px.swap(0, 2));
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8)
+        }
+        "rgba8" => ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba8),
+        "bgra8" => {
+            pixels.chunks_exact_mut(4).for_each(|px| px.swap(0, 2));
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba8)
+        }
+        "mono8" | "8UC1" => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8)
+        }
+        _ => {
+            let samples = pixels
+                .chunks_exact(2)
+                .map(|b| match image.is_bigendian {
+                    0 => u16::from_le_bytes([b[0], b[1]]),
+                    _ => u16::from_be_bytes([b[0], b[1]]),
+                })
+                .collect();
+            ImageBuffer::from_raw(width, height, samples).map(DynamicImage::ImageLuma16)
+        }
+    };
+    decoded.ok_or_else(|| "Image buffer does not match its size".to_string())
+}
+
+// The codec of a CompressedImage format such as `bgr8; jpeg compressed bgr8`
+fn compressed_format(format: &str) -> Option<ImageFormat> {
+    let codec = format.rsplit(';').next()?.split_whitespace().next()?;
+    match codec {
+        "jpeg" | "jpg" => Some(ImageFormat::Jpeg),
+        "png" => Some(ImageFormat::Png),
+        _ => None,
+    }
+}
+
+fn encode_image(image: DynamicImage, options: &ImageStreamOptions) -> Result<Frame, String> {
+    let image = if options.fits(image.width(), image.height()) {
+        image
+    } else {
+        let width = if options.max_width == 0 {
+            u32::MAX
+        } else {
+            options.max_width
+        };
+        let height = if options.max_height == 0 {
+            u32::MAX
+        } else {
+            options.max_height
+        };
+        image.resize(width, height, FilterType::Triangle)
+    };
+    let (width, height) = (image.width(), image.height());
+
+    let mut data = Vec::new();
+    let result = match options.format {
+        ImageFormat::Jpeg => {
+            // JPEG has neither alpha nor 16 bit samples
+            let image = match image.color() {
+                ColorType::L8 | ColorType::Rgb8 => image,
+                ColorType::L16 | ColorType::La8 | ColorType::La16 => {
+                    DynamicImage::ImageLuma8(image.to_luma8())
+                }
+                _ => DynamicImage::ImageRgb8(image.to_rgb8()),
+            };
+            let quality = match options.quality {
+                0 => DEFAULT_JPEG_QUALITY,
+                quality => quality,
+            };
+            image.write_with_encoder(JpegEncoder::new_with_quality(&mut data, quality))
+        }
+        ImageFormat::Png => image.write_with_encoder(PngEncoder::new(&mut data)),
+    };
+    result.map_err(|e| format!("Failed to encode {:?}: {}", options.format, e))?;
+
+    Ok(Frame {
+        header: std_msgs::Header::default(),
+        format: options.format,
+        width,
+        height,
+        data,
+    })
+}
+
+pub fn encode_frame(
+    payload: &[u8],
+    compressed: bool,
+    options: &ImageStreamOptions,
+) -> Result<Frame, String> {
+    if !compressed {
+        let image: sensor_msgs::Image =
+            cdr::deserialize(payload).map_err(|e| format!("Failed to decode Image: {}", e))?;
+        let mut frame = encode_image(decode_raw(&image)?, options)?;
+        frame.header = image.header;
+        return Ok(frame);
+    }
+
+    let image: sensor_msgs::CompressedImage = cdr::deserialize(payload)
+        .map_err(|e| format!("Failed to decode CompressedImage: {}", e))?;
+    let reader = ImageReader::new(Cursor::new(&image.data))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let (width, height) = reader
+        .into_dimensions()
+        .map_err(|e| format!("Failed to read {} frame: {}", image.format, e))?;
+
+    // Forward frames that already match what the client asked for without recompressing them
+    let mut frame = if compressed_format(&image.format) == Some(options.format)
+        && options.quality == 0
+        && options.fits(width, height)
+    {
+        Frame {
+            header: Default::default(),
+            format: options.format,
+            width,
+            height,
+            data: image.data,
+        }
+    } else {
+        let decoded = image::load_from_memory(&image.data)
+            .map_err(|e| format!("Failed to decode {} frame: {}", image.format, e))?;
+        encode_image(decoded, options)?
+    };
+    frame.header = image.header;
+    Ok(frame)
+}
+
+// Streams re-encoded frames to a client, unsubscribes from zenoh when canceled or dropped
+pub struct ImageZenohSubscription {
+    subscriber: Option<zenoh::pubsub::Subscriber<()>>,
+}
+
+impl ImageZenohSubscription {
+    pub async fn declare(
+        session: &zenoh::Session,
+        topic: &str,
+        compressed: bool,
+        options: ImageStreamOptions,
+        listener: image_listener::Client,
+    ) -> Result<Self, capnp::Error> {
+        // Only the latest frame is kept, frames arriving while the previous one is still
+        // being encoded or waits for the client's flow control window are dropped
+        let (tx, mut rx) = watch::channel(None::<Arc<[u8]>>);
+        let min_interval = options.min_interval;
+        let last_frame = Mutex::new(None::<Instant>);
+        let subscriber = session
+            .declare_subscriber(topic)
+            .callback(move |sample| {
+                if let Some(interval) = min_interval {
+                    let mut last = last_frame.lock().unwrap();
+                    let now = Instant::now();
+                    if last.is_some_and(|t| now.duration_since(t) < interval) {
+                        return;
+                    }
+                    *last = Some(now);
+                }
+                tx.send_replace(Some(Arc::from(&*sample.payload().to_bytes())));
+            })
+            .await
+            .map_err(|e| capnp::Error::failed(format!("Failed to subscribe {}: {}", topic, e)))?;
+
+        // The channel closes once the zenoh subscriber is dropped
+        let topic = topic.to_string();
+        let options = Arc::new(options);
+        tokio::task::spawn_local(async move {
+            while rx.changed().await.is_ok() {
+                let Some(payload) = rx.borrow_and_update().clone() else {
+                    continue;
+                };
+                let options = options.clone();
+                let encoded = tokio::task::spawn_blocking(move || {
+                    encode_frame(&payload, compressed, &options)
+                })
+                .await;
+                let frame = match encoded {
+                    Ok(Ok(frame)) => frame,
+                    Ok(Err(e)) => {
+                        eprintln!("Dropping frame from {}: {}", topic, e);
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!("Frame encoder for {} failed: {}", topic, e);
+                        break;
+                    }
+                };
+
+                let mut request = listener.on_frame_request();
+                frame.write(request.get().init_frame());
+                if let Err(e) = request.send().await {
+                    eprintln!("Failed to deliver frame to client: {}", e);
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            subscriber: Some(subscriber),
+        })
+    }
+}
+
+impl subscription::Server for ImageZenohSubscription {
+    fn cancel(
+        &mut self,
+        _: subscription::CancelParams,
+        _: subscription::CancelResults,
+    ) -> Promise<(), capnp::Error> {
+        self.subscriber.take();
+        Promise::ok(())
+    }
+}
@@ -0,0 +1,189 @@
+// Decoding raw frames and re-encoding them for image stream subscribers
+
+use cdr::{CdrLe, Infinite};
+use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+
+use crate::images::{ImageStreamOptions, decode_raw, encode_frame};
+use crate::ros_types::{sensor_msgs, std_msgs};
+use crate::schema_capnp::{ImageFormat, image_stream_options};
+
+fn raw(encoding: &str, width: u32, height: u32, step: u32, data: Vec<u8>) -> sensor_msgs::Image {
+    sensor_msgs::Image {
+        header: std_msgs::Header::default(),
+        height,
+        width,
+        encoding: encoding.to_string(),
+        is_bigendian: 0,
+        step,
+        data,
+    }
+}
+
+fn options(
+    format: ImageFormat,
+    quality: u8,
+    max_width: u32,
+    max_height: u32,
+) -> ImageStreamOptions {
+    ImageStreamOptions {
+        format,
+        quality,
+        max_width,
+        max_height,
+        min_interval: None,
+    }
+}
+
+fn raw_payload(width: u32, height: u32) -> Vec<u8> {
+    let image = raw(
+        "rgb8",
+        width,
+        height,
+        width * 3,
+        vec![128; (width * height * 3) as usize],
+    );
+    cdr::serialize::<_, _, CdrLe>(&image, Infinite).unwrap()
+}
+
+fn encoded(width: u32, height: u32, format: image::ImageFormat) -> Vec<u8> {
+    let mut data = Vec::new();
+    DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, Rgb([10, 20, 30])))
+        .write_to(&mut std::io::Cursor::new(&mut data), format)
+        .unwrap();
+    data
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    encoded(width, height, image::ImageFormat::Png)
+}
+
+fn compressed_payload(format: &str, data: Vec<u8>) -> Vec<u8> {
+    let image = sensor_msgs::CompressedImage {
+        header: std_msgs::Header::default(),
+        format: format.to_string(),
+        data,
+    };
+    cdr::serialize::<_, _, CdrLe>(&image, Infinite).unwrap()
+}
+
+#[test]
+fn drops_row_padding() {
+    // 2x2 mono8 with two bytes of padding per row
+    let image = raw("mono8", 2, 2, 4, vec![1, 2, 0xee, 0xee, 3, 4, 0xee, 0xee]);
+    let decoded = decode_raw(&image).unwrap().into_luma8();
+    assert_eq!(decoded.into_raw(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn rejects_data_shorter_than_step() {
+    let image = raw("mono8", 2, 2, 4, vec![1, 2, 0, 0, 3, 4]);
+    assert!(decode_raw(&image).is_err());
+}
+
+#[test]
+fn swaps_bgr_channels() {
+    let image = raw("bgr8", 1, 1, 3, vec![1, 2, 3]);
+    assert_eq!(
+        decode_raw(&image).unwrap().into_rgb8().into_raw(),
+        vec![3, 2, 1]
+    );
+
+    let image = raw("bgra8", 1, 1, 4, vec![1, 2, 3, 4]);
+    assert_eq!(
+        decode_raw(&image).unwrap().into_rgba8().into_raw(),
+        vec![3, 2, 1, 4]
+    );
+}
+
+#[test]
+fn reads_mono16_in_either_byte_order() {
+    let mut image = raw("mono16", 2, 1, 4, vec![0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(
+        decode_raw(&image).unwrap().into_luma16().into_raw(),
+        vec![0x0201, 0x0403]
+    );
+
+    image.is_bigendian = 1;
+    assert_eq!(
+        decode_raw(&image).unwrap().into_luma16().into_raw(),
+        vec![0x0102, 0x0304]
+    );
+}
+
+#[test]
+fn rejects_unknown_encodings() {
+    let image = raw("yuv422", 1, 1, 2, vec![0, 0]);
+    assert!(decode_raw(&image).is_err());
+}
+
+#[test]
+fn resizes_keeping_aspect_ratio() {
+    let frame = encode_frame(
+        &raw_payload(64, 32),
+        false,
+        &options(ImageFormat::Png, 0, 16, 16),
+    )
+    .unwrap();
+    assert_eq!((frame.width, frame.height), (16, 8));
+    let decoded = image::load_from_memory(&frame.data).unwrap();
+    assert_eq!(decoded.dimensions(), (16, 8));
+}
+
+#[test]
+fn zero_leaves_a_dimension_unbounded() {
+    let frame = encode_frame(
+        &raw_payload(64, 32),
+        false,
+        &options(ImageFormat::Png, 0, 0, 16),
+    )
+    .unwrap();
+    assert_eq!((frame.width, frame.height), (32, 16));
+
+    let frame = encode_frame(
+        &raw_payload(64, 32),
+        false,
+        &options(ImageFormat::Png, 0, 0, 0),
+    )
+    .unwrap();
+    assert_eq!((frame.width, frame.height), (64, 32));
+}
+
+#[test]
+fn forwards_matching_compressed_frames_untouched() {
+    let data = png(8, 4);
+    let payload = compressed_payload("rgb8; png compressed rgb8", data.clone());
+    let frame = encode_frame(&payload, true, &options(ImageFormat::Png, 0, 0, 0)).unwrap();
+    assert_eq!((frame.width, frame.height), (8, 4));
+    assert_eq!(frame.data, data);
+}
+
+#[test]
+fn recompresses_when_quality_is_set() {
+    let data = encoded(8, 4, image::ImageFormat::Jpeg);
+    let payload = compressed_payload("rgb8; jpeg compressed rgb8", data.clone());
+    let untouched = encode_frame(&payload, true, &options(ImageFormat::Jpeg, 0, 0, 0)).unwrap();
+    assert_eq!(untouched.data, data);
+
+    let frame = encode_frame(&payload, true, &options(ImageFormat::Jpeg, 10, 0, 0)).unwrap();
+    assert_ne!(frame.data, data);
+    assert_eq!((frame.width, frame.height), (8, 4));
+}
+
+#[test]
+fn recompresses_to_another_format() {
+    let payload = compressed_payload("rgb8; png compressed rgb8", png(8, 4));
+    let frame = encode_frame(&payload, true, &options(ImageFormat::Jpeg, 0, 0, 0)).unwrap();
+    assert_eq!(frame.format, ImageFormat::Jpeg);
+    assert_eq!(
+        image::guess_format(&frame.data).unwrap(),
+        image::ImageFormat::Jpeg
+    );
+}
+
+#[test]
+fn rejects_max_rates_overflowing_the_interval() {
+    let mut message = capnp::message::Builder::new_default();
+    let mut builder = message.init_root::<image_stream_options::Builder>();
+    builder.set_max_rate(1e-30);
+    assert!(ImageStreamOptions::read(builder.into_reader()).is_err());
+}
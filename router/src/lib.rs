@@ -0,0 +1,1054 @@
+// The router as a library, so it can be embedded and tested in-process
+
+use capnp::capability::Promise;
+use capnp::serialize_packed;
+use capnp_rpc::pry;
+use capnp_rpc::{RpcSystem, rpc_twoparty_capnp, twoparty};
+use cdr::{CdrLe, Infinite};
+use futures::AsyncReadExt;
+use tokio::sync::watch;
+
+pub use proto::{ros_capnp, ros_idl, ros_types, schema_capnp};
+
+mod action;
+#[cfg(test)]
+mod cdr_tests;
+pub mod clock;
+pub mod decode;
+#[cfg(test)]
+mod decode_tests;
+mod dynamic_cdr;
+mod images;
+#[cfg(test)]
+mod images_tests;
+mod messages;
+#[cfg(test)]
+mod messages_tests;
+mod odometry;
+#[cfg(test)]
+mod odometry_tests;
+mod parameters;
+mod projection;
+mod publisher;
+mod query;
+pub mod sink;
+#[cfg(test)]
+mod sink_tests;
+pub mod status;
+#[cfg(test)]
+mod status_tests;
+mod tf;
+#[cfg(test)]
+mod tf_tests;
+mod turtlesim;
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use action::{ActiveGoal, DynamicAction, GenericGoalHandle};
+use clock::{Clock, ClockSource};
+use images::{ImageStreamOptions, ImageZenohSubscription};
+use messages::{SubscriptionOptions, TypedSinkPublisher, TypedZenohSubscription};
+use odometry::{DeadReckoning, DeadReckoningService};
+use publisher::{GenericSinkPublisher, read_value};
+pub use ros_idl::TypeRegistry;
+use ros_types::{builtin_interfaces, geometry_msgs, std_msgs};
+use schema_capnp::bootstrap;
+use schema_capnp::hello_service;
+use schema_capnp::twist_service;
+use sink::{Encoding, Message, MessageSink, Qos, RouterPublisher, Source, ZenohSink};
+use status::{StatusMonitor, StatusSubscription};
+use tf::TfListener;
+use turtlesim::TurtlesimZenohService;
+
+// Separate service structs for different service types
+struct HelloSinkService {
+    sink: Arc<dyn MessageSink>,
+    topic: String,
+    qos: Qos,
+    source: Arc<Source>,
+}
+
+// How twists are sent, shared by every twist service
+#[derive(Clone)]
+struct TwistOptions {
+    sink: Arc<dyn MessageSink>,
+    qos: Qos,
+    source: Arc<Source>,
+    clock: Clock,
+    // Robots that expect geometry_msgs/TwistStamped instead of a bare Twist
+    stamped: Arc<HashSet<String>>,
+    dead_reckoning: Option<Arc<DeadReckoning>>,
+}
+
+struct TwistSinkService {
+    sink: Arc<dyn MessageSink>,
+    qos: Qos,
+    source: Arc<Source>,
+    topic: String,
+    robot: String,
+    clock: Clock,
+    stamped: bool,
+    dead_reckoning: Option<Arc<DeadReckoning>>,
+}
+
+impl TwistSinkService {
+    fn new(topic: &str, options: &TwistOptions) -> Self {
+        let robot = topic.strip_suffix("/cmd_vel").unwrap_or(topic);
+        Self {
+            sink: options.sink.clone(),
+            qos: options.qos,
+            source: options.source.clone(),
+            topic: topic.to_string(),
+            robot: robot.to_string(),
+            clock: options.clock.clone(),
+            stamped: options.stamped.contains(robot),
+            dead_reckoning: options.dead_reckoning.clone(),
+        }
+    }
+
+    fn for_robot(robot: &str, options: &TwistOptions) -> Self {
+        Self::new(&format!("{}/cmd_vel", robot), options)
+    }
+}
+
+impl hello_service::Server for HelloSinkService {
+    fn do_hello(
+        &mut self,
+        params: hello_service::DoHelloParams,
+        _results: hello_service::DoHelloResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        // Alternative approach - get the data field and serialize it
+        let params_reader = pry!(params.get());
+        let data = pry!(params_reader.get_data());
+
+        // Create a new message with just the data field
+        let mut message = capnp::message::Builder::new_default();
+        message.set_root(data.reborrow()).unwrap();
+
+        let mut buffer = Vec::new();
+        serialize_packed::write_message(&mut buffer, &message).unwrap();
+
+        let topic = self.topic.clone();
+        // Send the raw bytes directly
+        let published = self.sink.publish(Message {
+            key: topic.clone(),
+            payload: buffer,
+            encoding: Encoding::CapnpPacked,
+            qos: self.qos,
+            origin: self.source.origin(&topic),
+        });
+        // Awaited in the call, so a client's messages go out in the order they were numbered
+        Promise::from_future(async move {
+            published.await?;
+            println!("Raw Cap'n Proto data sent on {} topic", topic);
+            Ok(())
+        })
+    }
+}
+
+impl twist_service::Server for TwistSinkService {
+    fn do_twist(
+        &mut self,
+        params: twist_service::DoTwistParams,
+        _: twist_service::DoTwistResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let twist = pry!(geometry_msgs::Twist::read(pry!(
+            pry!(params.get()).get_data()
+        )));
+
+        println!(
+            "Publishing twist message: x {} y {} z {} angular: x {} y {} z {}",
+            twist.linear.x,
+            twist.linear.y,
+            twist.linear.z,
+            twist.angular.x,
+            twist.angular.y,
+            twist.angular.z
+        );
+        if let Some(ref dead_reckoning) = self.dead_reckoning {
+            dead_reckoning.command(&self.robot, &twist);
+        }
+
+        let (encoded, ros_type) = if self.stamped {
+            let stamped = geometry_msgs::TwistStamped {
+                header: std_msgs::Header {
+                    stamp: self.clock.stamp(),
+                    frame_id: String::new(),
+                },
+                twist,
+            };
+            let encoded = cdr::serialize::<_, _, CdrLe>(&stamped, Infinite).unwrap();
+            (encoded, "geometry_msgs/msg/TwistStamped")
+        } else {
+            let encoded = cdr::serialize::<_, _, CdrLe>(&twist, Infinite).unwrap();
+            (encoded, "geometry_msgs/msg/Twist")
+        };
+
+        let topic = self.topic.clone();
+        let published = self.sink.publish(Message {
+            key: topic.clone(),
+            payload: encoded,
+            encoding: Encoding::cdr(ros_type),
+            qos: self.qos,
+            origin: self.source.origin(&topic),
+        });
+        Promise::from_future(async move {
+            published.await?;
+            println!("Twist sent on {} topic", topic);
+            Ok(())
+        })
+    }
+}
+
+// Services whose messages go through the sink, and can have their own QoS. Clock, tf and
+// deadReckoning are what the router publishes itself: the clock, turtle transforms and odometry
+const PUBLISHING_SERVICES: [&str; 7] = [
+    "hello",
+    "twist",
+    "publisher",
+    "messages",
+    "clock",
+    "tf",
+    "deadReckoning",
+];
+
+// Builder for creating the bootstrap service with configured publishers
+pub struct BootstrapServiceBuilder {
+    zenoh_session: zenoh::Session,
+    hello_topic: Option<String>,
+    twist_topic: Option<String>,
+    turtlesim: bool,
+    parameters: bool,
+    registry: Option<Arc<TypeRegistry>>,
+    messages: Option<Arc<TypeRegistry>>,
+    actions: Option<Arc<TypeRegistry>>,
+    images: bool,
+    tf: bool,
+    turtle_tf_frame: Option<String>,
+    stamped_twist: HashSet<String>,
+    dead_reckoning_key: Option<String>,
+    clock: ClockSource,
+    sink: Option<Arc<dyn MessageSink>>,
+    qos: HashMap<String, Qos>,
+    status: bool,
+}
+
+impl BootstrapServiceBuilder {
+    pub fn new(zenoh_session: zenoh::Session) -> Self {
+        Self {
+            zenoh_session,
+            hello_topic: None,
+            twist_topic: None,
+            turtlesim: false,
+            parameters: false,
+            registry: None,
+            messages: None,
+            actions: None,
+            images: false,
+            tf: false,
+            turtle_tf_frame: None,
+            stamped_twist: HashSet::new(),
+            dead_reckoning_key: None,
+            clock: ClockSource::Wall,
+            sink: None,
+            qos: HashMap::new(),
+            status: false,
+        }
+    }
+
+    pub fn with_hello_publisher(mut self, topic: impl Into<String>) -> Self {
+        self.hello_topic = Some(topic.into());
+        self
+    }
+
+    pub fn with_twist_publisher(mut self, topic: impl Into<String>) -> Self {
+        self.twist_topic = Some(topic.into());
+        self
+    }
+
+    pub fn with_turtlesim_service(mut self) -> Self {
+        self.turtlesim = true;
+        self
+    }
+
+    pub fn with_parameter_services(mut self) -> Self {
+        self.parameters = true;
+        self
+    }
+
+    // Enables publishers for any ROS type defined in the registry
+    pub fn with_generic_publishers(mut self, registry: Arc<TypeRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    // Enables typed publishers and subscriptions for the message types in MessageType, the
+    // registry resolves the fields projected by subscriptions
+    pub fn with_message_services(mut self, registry: Arc<TypeRegistry>) -> Self {
+        self.messages = Some(registry);
+        self
+    }
+
+    // Enables goals for any ROS action type defined in the registry
+    pub fn with_generic_actions(mut self, registry: Arc<TypeRegistry>) -> Self {
+        self.actions = Some(registry);
+        self
+    }
+
+    // Enables streaming of camera topics, re-encoded per subscriber
+    pub fn with_image_streams(mut self) -> Self {
+        self.images = true;
+        self
+    }
+
+    // Buffers /tf and /tf_static for transform lookups
+    pub fn with_tf_buffer(mut self) -> Self {
+        self.tf = true;
+        self
+    }
+
+    // Broadcasts a transform from `parent_frame` to every turtle, computed from its pose
+    pub fn with_turtle_tf(mut self, parent_frame: impl Into<String>) -> Self {
+        self.turtle_tf_frame = Some(parent_frame.into());
+        self
+    }
+
+    // Sends geometry_msgs/TwistStamped on the robot's cmd_vel, as newer controllers expect
+    pub fn with_stamped_twist(mut self, robot: impl Into<String>) -> Self {
+        self.stamped_twist.insert(robot.into());
+        self
+    }
+
+    // Integrates the commanded twists into nav_msgs/Odometry published on `key`, where
+    // `{robot}` is replaced by the robot name
+    pub fn with_dead_reckoning(mut self, key: impl Into<String>) -> Self {
+        self.dead_reckoning_key = Some(key.into());
+        self
+    }
+
+    // Time used for the stamps the router generates and its timeouts
+    pub fn with_clock(mut self, clock: ClockSource) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    // Where published messages go, a ZenohSink on the builder's session by default
+    pub fn with_sink(mut self, sink: Arc<dyn MessageSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    // QoS of what `service` publishes, one of the publishing services reported by getInfo
+    pub fn with_service_qos(mut self, service: impl Into<String>, qos: Qos) -> Self {
+        self.qos.insert(service.into(), qos);
+        self
+    }
+
+    // Monitors the session's transports and subscribers to the hello and twist topics
+    pub fn with_status_monitor(mut self) -> Self {
+        self.status = true;
+        self
+    }
+
+    pub async fn build(self) -> Result<BootstrapService, Box<dyn std::error::Error>> {
+        if let Some(service) = self
+            .qos
+            .keys()
+            .find(|service| !PUBLISHING_SERVICES.contains(&service.as_str()))
+        {
+            return Err(format!(
+                "QoS configured for {}, which does not publish, expected one of {}",
+                service,
+                PUBLISHING_SERVICES.join(", ")
+            )
+            .into());
+        }
+        let mut features = Vec::new();
+        match self.clock {
+            ClockSource::Wall => {}
+            ClockSource::Sim => features.push("simTime"),
+            ClockSource::Publish { .. } => features.push("publishedClock"),
+        }
+        if self.turtle_tf_frame.is_some() {
+            features.push("turtleTf");
+        }
+        if !self.stamped_twist.is_empty() {
+            features.push("stampedTwist");
+        }
+        let sink = self
+            .sink
+            .unwrap_or_else(|| Arc::new(ZenohSink::new(self.zenoh_session.clone())));
+        let publisher = |service: &str, source: &Arc<Source>| RouterPublisher {
+            sink: sink.clone(),
+            qos: self.qos.get(service).copied().unwrap_or_default(),
+            source: source.clone(),
+        };
+        // A published clock cannot stamp its own samples, they carry wall time
+        let clock_source = Arc::new(Source::new("router"));
+        let clock = Clock::start(
+            &self.zenoh_session,
+            self.clock,
+            publisher("clock", &clock_source),
+        )
+        .await?;
+        // Numbers what the router publishes on its own, apart from any client
+        let router_source = Arc::new(Source::new("router").with_clock(clock.clone()));
+        let router_publisher = |service: &str| publisher(service, &router_source);
+        // Replaced by each connection's own, see BootstrapService::for_client
+        let source = Arc::new(Source::new("router").with_clock(clock.clone()));
+        let tf = match self.tf {
+            true => Some(Arc::new(TfListener::declare(&self.zenoh_session).await?)),
+            false => None,
+        };
+        let turtle_tf = match self.turtle_tf_frame {
+            Some(frame) => {
+                let broadcaster = tf::broadcast_turtles(
+                    &self.zenoh_session,
+                    frame,
+                    clock.clone(),
+                    router_publisher("tf"),
+                )
+                .await?;
+                Some(Arc::new(broadcaster))
+            }
+            None => None,
+        };
+        let (dead_reckoning, pose_subscriber) = match self.dead_reckoning_key {
+            Some(key) => {
+                // Turtlesim stops a turtle one second after its last command
+                let dead_reckoning =
+                    Arc::new(DeadReckoning::new(clock.clone(), Duration::from_secs(1)));
+                let subscriber = odometry::start(
+                    &self.zenoh_session,
+                    dead_reckoning.clone(),
+                    key,
+                    router_publisher("deadReckoning"),
+                )
+                .await?;
+                (Some(dead_reckoning), Some(Arc::new(subscriber)))
+            }
+            None => (None, None),
+        };
+        let status = match self.status {
+            true => {
+                let topics = self.hello_topic.iter().chain(&self.twist_topic).cloned();
+                Some(Arc::new(
+                    StatusMonitor::start(&self.zenoh_session, topics).await?,
+                ))
+            }
+            false => None,
+        };
+
+        Ok(BootstrapService {
+            zenoh_session: self.zenoh_session,
+            hello_topic: self.hello_topic,
+            twist_topic: self.twist_topic,
+            turtlesim: self.turtlesim,
+            parameters: self.parameters,
+            registry: self.registry,
+            messages: self.messages,
+            actions: self.actions,
+            images: self.images,
+            tf,
+            _turtle_tf: turtle_tf,
+            _dead_reckoning_poses: pose_subscriber,
+            features,
+            sink: sink.clone(),
+            qos: self.qos.clone(),
+            source: source.clone(),
+            status,
+            twist_options: TwistOptions {
+                sink,
+                qos: self.qos.get("twist").copied().unwrap_or_default(),
+                source,
+                clock,
+                stamped: Arc::new(self.stamped_twist),
+                dead_reckoning,
+            },
+        })
+    }
+}
+
+// Bootstrap service that provides access to the configured services, cloned for every
+// connection with the connection's own source
+#[derive(Clone)]
+pub struct BootstrapService {
+    zenoh_session: zenoh::Session,
+    hello_topic: Option<String>,
+    twist_topic: Option<String>,
+    turtlesim: bool,
+    parameters: bool,
+    registry: Option<Arc<TypeRegistry>>,
+    messages: Option<Arc<TypeRegistry>>,
+    actions: Option<Arc<TypeRegistry>>,
+    images: bool,
+    tf: Option<Arc<TfListener>>,
+    _turtle_tf: Option<Arc<zenoh::pubsub::Subscriber<()>>>,
+    _dead_reckoning_poses: Option<Arc<zenoh::pubsub::Subscriber<()>>>,
+    features: Vec<&'static str>,
+    sink: Arc<dyn MessageSink>,
+    qos: HashMap<String, Qos>,
+    source: Arc<Source>,
+    status: Option<Arc<StatusMonitor>>,
+    twist_options: TwistOptions,
+}
+
+impl BootstrapService {
+    // The same services for another RPC client
+    pub fn for_client(&self, client_id: impl Into<String>) -> Self {
+        let source = Arc::new(Source::new(client_id).with_clock(self.twist_options.clock.clone()));
+        let mut service = self.clone();
+        service.source = source.clone();
+        service.twist_options.source = source;
+        service
+    }
+
+    fn qos(&self, service: &str) -> Qos {
+        self.qos.get(service).copied().unwrap_or_default()
+    }
+
+    // Named after the Bootstrap methods they enable, as reported by getInfo
+    fn services(&self) -> Vec<&'static str> {
+        let configured = [
+            ("hello", self.hello_topic.is_some()),
+            ("twist", self.twist_topic.is_some()),
+            ("turtlesim", self.turtlesim),
+            ("parameters", self.parameters),
+            ("publisher", self.registry.is_some()),
+            ("messages", self.messages.is_some()),
+            ("sendGoal", self.actions.is_some()),
+            ("images", self.images),
+            ("tf", self.tf.is_some()),
+            ("deadReckoning", self.twist_options.dead_reckoning.is_some()),
+            // Some for sinks keeping history, whatever the key
+            ("latest", self.sink.latest("").is_some()),
+            ("status", self.status.is_some()),
+        ];
+        configured
+            .into_iter()
+            .filter_map(|(name, enabled)| enabled.then_some(name))
+            .collect()
+    }
+
+    fn check_parameters(&self) -> Result<(), capnp::Error> {
+        if self.parameters {
+            Ok(())
+        } else {
+            Err(capnp::Error::failed(
+                "Parameter services not configured".to_string(),
+            ))
+        }
+    }
+
+    fn check_messages(&self) -> Result<&Arc<TypeRegistry>, capnp::Error> {
+        self.messages
+            .as_ref()
+            .ok_or_else(|| capnp::Error::failed("Message services not configured".to_string()))
+    }
+}
+
+impl bootstrap::Server for BootstrapService {
+    fn get_hello_service(
+        &mut self,
+        _params: bootstrap::GetHelloServiceParams,
+        mut results: bootstrap::GetHelloServiceResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        if let Some(ref topic) = self.hello_topic {
+            let hello_service = capnp_rpc::new_client(HelloSinkService {
+                sink: self.sink.clone(),
+                topic: topic.clone(),
+                qos: self.qos("hello"),
+                source: self.source.clone(),
+            });
+            results.get().set_service(hello_service);
+            Promise::ok(())
+        } else {
+            Promise::err(capnp::Error::failed(
+                "Hello publisher not configured".to_string(),
+            ))
+        }
+    }
+
+    fn get_twist_service(
+        &mut self,
+        _params: bootstrap::GetTwistServiceParams,
+        mut results: bootstrap::GetTwistServiceResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        if let Some(ref topic) = self.twist_topic {
+            let twist_service =
+                capnp_rpc::new_client(TwistSinkService::new(topic, &self.twist_options));
+            results.get().set_service(twist_service);
+            Promise::ok(())
+        } else {
+            Promise::err(capnp::Error::failed(
+                "Twist publisher not configured".to_string(),
+            ))
+        }
+    }
+
+    fn get_turtlesim_service(
+        &mut self,
+        _params: bootstrap::GetTurtlesimServiceParams,
+        mut results: bootstrap::GetTurtlesimServiceResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        if self.turtlesim {
+            let turtlesim_service = capnp_rpc::new_client(TurtlesimZenohService {
+                session: self.zenoh_session.clone(),
+                twist_options: self.twist_options.clone(),
+            });
+            results.get().set_service(turtlesim_service);
+            Promise::ok(())
+        } else {
+            Promise::err(capnp::Error::failed(
+                "Turtlesim service not configured".to_string(),
+            ))
+        }
+    }
+
+    fn get_parameters(
+        &mut self,
+        params: bootstrap::GetParametersParams,
+        mut results: bootstrap::GetParametersResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        pry!(self.check_parameters());
+        let params = pry!(params.get());
+        let node = pry!(pry!(params.get_node()).to_string());
+        let names = pry!(
+            pry!(params.get_names())
+                .iter()
+                .map(|name| Ok(name?.to_string()?))
+                .collect::<Result<Vec<_>, capnp::Error>>()
+        );
+
+        let session = self.zenoh_session.clone();
+        Promise::from_future(async move {
+            let values = parameters::get_parameters(&session, &node, names).await?;
+            let mut list = results.get().init_values(values.len() as u32);
+            for (i, value) in values.iter().enumerate() {
+                value.write(list.reborrow().get(i as u32));
+            }
+            Ok(())
+        })
+    }
+
+    fn set_parameters(
+        &mut self,
+        params: bootstrap::SetParametersParams,
+        mut results: bootstrap::SetParametersResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        pry!(self.check_parameters());
+        let params = pry!(params.get());
+        let node = pry!(pry!(params.get_node()).to_string());
+        let parameters = pry!(
+            pry!(params.get_parameters())
+                .iter()
+                .map(parameters::Parameter::read)
+                .collect::<Result<Vec<_>, capnp::Error>>()
+        );
+
+        let session = self.zenoh_session.clone();
+        Promise::from_future(async move {
+            let outcomes = parameters::set_parameters(&session, &node, parameters).await?;
+            let mut list = results.get().init_results(outcomes.len() as u32);
+            for (i, outcome) in outcomes.iter().enumerate() {
+                outcome.write(list.reborrow().get(i as u32));
+            }
+            Ok(())
+        })
+    }
+
+    fn list_parameters(
+        &mut self,
+        params: bootstrap::ListParametersParams,
+        mut results: bootstrap::ListParametersResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        pry!(self.check_parameters());
+        let node = pry!(pry!(pry!(params.get()).get_node()).to_string());
+
+        let session = self.zenoh_session.clone();
+        Promise::from_future(async move {
+            let listed = parameters::list_parameters(&session, &node).await?;
+            let mut results = results.get();
+            let mut names = results.reborrow().init_names(listed.names.len() as u32);
+            for (i, name) in listed.names.iter().enumerate() {
+                names.set(i as u32, name);
+            }
+            let mut prefixes = results.init_prefixes(listed.prefixes.len() as u32);
+            for (i, prefix) in listed.prefixes.iter().enumerate() {
+                prefixes.set(i as u32, prefix);
+            }
+            Ok(())
+        })
+    }
+
+    fn get_publisher(
+        &mut self,
+        params: bootstrap::GetPublisherParams,
+        mut results: bootstrap::GetPublisherResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let Some(ref registry) = self.registry else {
+            return Promise::err(capnp::Error::failed(
+                "Generic publishers not configured".to_string(),
+            ));
+        };
+        let params = pry!(params.get());
+        let topic = pry!(pry!(params.get_topic()).to_string());
+        let ros_type = pry!(pry!(params.get_ros_type()).to_string());
+        // Resolve the type now so clients learn about typos before publishing
+        let Some(def) = registry.get(&ros_type) else {
+            return Promise::err(capnp::Error::failed(format!(
+                "Unknown ROS type {}",
+                ros_type
+            )));
+        };
+
+        let publisher = capnp_rpc::new_client(GenericSinkPublisher {
+            sink: self.sink.clone(),
+            qos: self.qos("publisher"),
+            source: self.source.clone(),
+            topic,
+            ros_type: def.name.clone(),
+            registry: registry.clone(),
+        });
+        results.get().set_publisher(publisher);
+        Promise::ok(())
+    }
+
+    fn get_message_publisher(
+        &mut self,
+        params: bootstrap::GetMessagePublisherParams,
+        mut results: bootstrap::GetMessagePublisherResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        pry!(self.check_messages());
+        let params = pry!(params.get());
+        let publisher = capnp_rpc::new_client(TypedSinkPublisher {
+            sink: self.sink.clone(),
+            qos: self.qos("messages"),
+            source: self.source.clone(),
+            topic: pry!(pry!(params.get_topic()).to_string()),
+            message_type: pry!(params.get_message_type()),
+        });
+        results.get().set_publisher(publisher);
+        Promise::ok(())
+    }
+
+    fn subscribe(
+        &mut self,
+        params: bootstrap::SubscribeParams,
+        mut results: bootstrap::SubscribeResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let registry = pry!(self.check_messages()).clone();
+        let params = pry!(params.get());
+        let topic = pry!(pry!(params.get_topic()).to_string());
+        let message_type = pry!(params.get_message_type());
+        let listener = pry!(params.get_listener());
+        let options = if params.has_options() {
+            pry!(SubscriptionOptions::read(
+                pry!(params.get_options()),
+                &registry,
+                message_type
+            ))
+        } else {
+            SubscriptionOptions::default()
+        };
+
+        let session = self.zenoh_session.clone();
+        Promise::from_future(async move {
+            let subscription = TypedZenohSubscription::declare(
+                &session,
+                &topic,
+                message_type,
+                options,
+                registry,
+                listener,
+            )
+            .await?;
+            results
+                .get()
+                .set_subscription(capnp_rpc::new_client(subscription));
+            Ok(())
+        })
+    }
+
+    fn subscribe_images(
+        &mut self,
+        params: bootstrap::SubscribeImagesParams,
+        mut results: bootstrap::SubscribeImagesResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        if !self.images {
+            return Promise::err(capnp::Error::failed(
+                "Image streams not configured".to_string(),
+            ));
+        }
+        let params = pry!(params.get());
+        let topic = pry!(pry!(params.get_topic()).to_string());
+        let compressed = params.get_compressed();
+        let listener = pry!(params.get_listener());
+        let options = pry!(ImageStreamOptions::read(pry!(params.get_options())));
+
+        let session = self.zenoh_session.clone();
+        Promise::from_future(async move {
+            let subscription =
+                ImageZenohSubscription::declare(&session, &topic, compressed, options, listener)
+                    .await?;
+            results
+                .get()
+                .set_subscription(capnp_rpc::new_client(subscription));
+            Ok(())
+        })
+    }
+
+    fn lookup_transform(
+        &mut self,
+        params: bootstrap::LookupTransformParams,
+        mut results: bootstrap::LookupTransformResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let Some(ref tf) = self.tf else {
+            return Promise::err(capnp::Error::failed("TF buffer not configured".to_string()));
+        };
+        let params = pry!(params.get());
+        let target = pry!(pry!(params.get_target()).to_string());
+        let source = pry!(pry!(params.get_source()).to_string());
+        let time = pry!(builtin_interfaces::Time::read(pry!(params.get_time())));
+
+        let (transform, time) = pry!(
+            tf.buffer
+                .lock()
+                .unwrap()
+                .lookup(&target, &source, tf::to_nanos(&time))
+                .map_err(|e| capnp::Error::failed(e.to_string()))
+        );
+        let transform = geometry_msgs::TransformStamped {
+            header: std_msgs::Header {
+                stamp: tf::from_nanos(time),
+                frame_id: target,
+            },
+            child_frame_id: source,
+            transform: transform.into(),
+        };
+        transform.write(results.get().init_transform());
+        Promise::ok(())
+    }
+
+    fn get_dead_reckoning(
+        &mut self,
+        params: bootstrap::GetDeadReckoningParams,
+        mut results: bootstrap::GetDeadReckoningResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let Some(ref dead_reckoning) = self.twist_options.dead_reckoning else {
+            return Promise::err(capnp::Error::failed(
+                "Dead reckoning not configured".to_string(),
+            ));
+        };
+        let robot = pry!(pry!(pry!(params.get()).get_robot()).to_string());
+        let service = capnp_rpc::new_client(DeadReckoningService {
+            dead_reckoning: dead_reckoning.clone(),
+            robot,
+        });
+        results.get().set_service(service);
+        Promise::ok(())
+    }
+
+    fn get_info(
+        &mut self,
+        _: bootstrap::GetInfoParams,
+        mut results: bootstrap::GetInfoResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let services = self.services();
+        let mut info = results.get().init_info();
+        info.set_router_version(env!("CARGO_PKG_VERSION"));
+        info.set_schema_version(schema_capnp::SCHEMA_VERSION);
+        let mut list = info.reborrow().init_services(services.len() as u32);
+        for (i, service) in services.iter().enumerate() {
+            list.set(i as u32, *service);
+        }
+        let mut list = info.init_features(self.features.len() as u32);
+        for (i, feature) in self.features.iter().enumerate() {
+            list.set(i as u32, *feature);
+        }
+        Promise::ok(())
+    }
+
+    fn get_latest(
+        &mut self,
+        params: bootstrap::GetLatestParams,
+        mut results: bootstrap::GetLatestResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let topic = pry!(pry!(pry!(params.get()).get_topic()).to_str());
+        let Some(samples) = self.sink.latest(topic) else {
+            return Promise::err(capnp::Error::failed(
+                "Last value cache not configured".to_string(),
+            ));
+        };
+        let mut list = results.get().init_samples(samples.len() as u32);
+        for (i, sample) in samples.iter().enumerate() {
+            let mut entry = list.reborrow().get(i as u32);
+            let message = &sample.message;
+            entry.set_key(&message.key);
+            entry.set_payload(&message.payload);
+            entry.set_encoding(message.encoding.to_string());
+            entry.set_client_id(&message.origin.client_id);
+            entry.set_sequence(message.origin.sequence);
+            entry.set_received_at(message.origin.received_at);
+            entry.set_timestamp(sample.timestamp.to_string());
+        }
+        Promise::ok(())
+    }
+
+    fn watch_status(
+        &mut self,
+        params: bootstrap::WatchStatusParams,
+        mut results: bootstrap::WatchStatusResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let Some(ref status) = self.status else {
+            return Promise::err(capnp::Error::failed(
+                "Status monitor not configured".to_string(),
+            ));
+        };
+        let listener = pry!(pry!(params.get()).get_listener());
+        let subscription = StatusSubscription::start(status.watch(), listener);
+        results
+            .get()
+            .set_subscription(capnp_rpc::new_client(subscription));
+        Promise::ok(())
+    }
+
+    fn send_goal(
+        &mut self,
+        params: bootstrap::SendGoalParams,
+        mut results: bootstrap::SendGoalResults,
+    ) -> capnp::capability::Promise<(), capnp::Error> {
+        let Some(ref registry) = self.actions else {
+            return Promise::err(capnp::Error::failed(
+                "Generic actions not configured".to_string(),
+            ));
+        };
+        let params = pry!(params.get());
+        let action = pry!(pry!(params.get_action()).to_str())
+            .trim_matches('/')
+            .to_string();
+        if action.is_empty() {
+            return Promise::err(capnp::Error::failed("Action name is empty".to_string()));
+        }
+        // Resolve the type now so clients learn about typos before the goal is sent
+        let action_type = pry!(pry!(params.get_action_type()).to_str());
+        let types = pry!(DynamicAction::resolve(registry.clone(), action_type));
+        let goal = pry!(read_value(pry!(params.get_goal())));
+        let callback = pry!(params.get_feedback());
+
+        let session = self.zenoh_session.clone();
+        Promise::from_future(async move {
+            let (goal, feedback) =
+                ActiveGoal::send_dynamic(&session, &action, &types, &goal).await?;
+            GenericGoalHandle::forward_feedback(feedback, callback);
+            results
+                .get()
+                .set_goal(capnp_rpc::new_client(GenericGoalHandle { goal, types }));
+            Ok(())
+        })
+    }
+}
+
+// Where the router listens and which services its bootstrap capability offers
+pub struct RouterConfig {
+    pub listen: String,
+    pub services: BootstrapServiceBuilder,
+}
+
+impl RouterConfig {
+    pub fn new(services: BootstrapServiceBuilder) -> Self {
+        Self {
+            listen: "0.0.0.0:7000".to_string(),
+            services,
+        }
+    }
+
+    // Port 0 binds an ephemeral port, see Router::local_addr
+    pub fn with_listen(mut self, addr: impl Into<String>) -> Self {
+        self.listen = addr.into();
+        self
+    }
+}
+
+// A running router, shut down by `shutdown` or when dropped
+pub struct Router {
+    local_addr: SocketAddr,
+    shutdown: watch::Sender<bool>,
+    accept_loop: tokio::task::JoinHandle<()>,
+}
+
+impl Router {
+    // Starts accepting RPC connections. Cap'n Proto clients are not Send, so this has to be
+    // called from within a tokio LocalSet
+    pub async fn serve(config: RouterConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let bootstrap_service = config.services.build().await?;
+
+        let listener = tokio::net::TcpListener::bind(&config.listen).await?;
+        let local_addr = listener.local_addr()?;
+        println!("Listening on {}", local_addr);
+
+        let (shutdown, mut stopped) = watch::channel(false);
+        let connections = shutdown.subscribe();
+        let accept_loop = tokio::task::spawn_local(async move {
+            loop {
+                let (stream, addr) = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => {
+                            eprintln!("Failed to accept connection: {}", e);
+                            continue;
+                        }
+                    },
+                    // Also ends when the router is dropped
+                    _ = stopped.changed() => break,
+                };
+                if let Err(e) = stream.set_nodelay(true) {
+                    eprintln!("Failed to set TCP_NODELAY: {}", e);
+                }
+                let (reader, writer) =
+                    tokio_util::compat::TokioAsyncReadCompatExt::compat(stream).split();
+                let network = twoparty::VatNetwork::new(
+                    futures::io::BufReader::new(reader),
+                    futures::io::BufWriter::new(writer),
+                    rpc_twoparty_capnp::Side::Server,
+                    Default::default(),
+                );
+
+                // Identifies the client in the attachments of what it publishes
+                let client_id = uuid::Uuid::new_v4().to_string();
+                println!("Client {} connected from {}", client_id, addr);
+                let bootstrap_client: bootstrap::Client =
+                    capnp_rpc::new_client(bootstrap_service.for_client(client_id));
+                let rpc = RpcSystem::new(Box::new(network), Some(bootstrap_client.client));
+                let mut stopped = connections.clone();
+                tokio::task::spawn_local(async move {
+                    tokio::select! {
+                        result = rpc => if let Err(e) = result {
+                            eprintln!("RPC connection failed: {}", e);
+                        },
+                        _ = stopped.changed() => {}
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            local_addr,
+            shutdown,
+            accept_loop,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    // Stops accepting connections and closes the open ones
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        let _ = self.accept_loop.await;
+    }
+}
@@ -1,267 +1,145 @@
-use capnp::capability::Promise;
-use capnp::serialize_packed;
-use capnp_rpc::pry;
-use capnp_rpc::{RpcSystem, rpc_twoparty_capnp, twoparty};
-use cdr::{CdrLe, Infinite};
-use futures::AsyncReadExt;
-use serde::{Deserialize, Serialize};
-use zenoh::{Config as ZenohConfig, try_init_log_from_env};
-
-pub mod schema_capnp {
-    include!("rpc/schema_capnp.rs");
-}
-
-use schema_capnp::bootstrap;
-use schema_capnp::hello;
-use schema_capnp::hello_service;
-use schema_capnp::twist_service;
-
-// Separate service structs for different service types
-struct HelloZenohService {
-    session: zenoh::Session,
-    topic: String,
-}
-
-struct TwistZenohService {
-    session: zenoh::Session,
-    topic: String,
-}
-
-#[derive(Deserialize, Serialize, PartialEq)]
-struct Hello {
-    data: String,
-}
-
-#[derive(Deserialize, Serialize, PartialEq)]
-struct Vector3 {
-    x: f64,
-    y: f64,
-    z: f64,
-}
-
-#[derive(Deserialize, Serialize, PartialEq)]
-struct Twist {
-    linear: Vector3,
-    angular: Vector3,
-}
-
-impl hello_service::Server for HelloZenohService {
-    fn do_hello(
-        &mut self,
-        params: hello_service::DoHelloParams,
-        _results: hello_service::DoHelloResults,
-    ) -> capnp::capability::Promise<(), capnp::Error> {
-        // Alternative approach - get the data field and serialize it
-        let params_reader = pry!(params.get());
-        let data = pry!(params_reader.get_data());
-        dbg!("received", &data);
-
-        // Create a new message with just the data field
-        let mut message = capnp::message::Builder::new_default();
-        message.set_root(data.reborrow()).unwrap();
-
-        let mut buffer = Vec::new();
-        serialize_packed::write_message(&mut buffer, &message).unwrap();
+use std::path::Path;
+use std::sync::Arc;
 
-        let session = self.session.clone();
-        let topic = self.topic.clone();
+use router::clock::ClockSource;
+use router::sink::{Buffering, Qos, SinkConfig, ZenohSink};
+use router::{BootstrapServiceBuilder, Router, RouterConfig, TypeRegistry};
+use zenoh::key_expr::OwnedKeyExpr;
+use zenoh::{Config as ZenohConfig, try_init_log_from_env};
 
-        tokio::spawn(async move {
-            // Send the raw bytes directly to Zenoh
-            match session.put(&topic, buffer).await {
-                Ok(_) => println!("Raw Cap'n Proto data sent to zenoh on {} topic", topic),
-                Err(e) => {
-                    eprintln!("Failed to publish raw data to zenoh: {}", e)
-                }
-            }
-        });
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // --dry-run prints what would be published and keeps the session off the network
+    let dry_run = std::env::args().any(|arg| arg == "--dry-run");
+    let sink = match std::env::var("MESSAGE_SINK") {
+        Ok(sink) => sink.parse::<SinkConfig>()?,
+        Err(_) if dry_run => SinkConfig::Stdout,
+        Err(_) => SinkConfig::Zenoh,
+    };
+    // Samples kept per published key, answered to gets and Bootstrap.getLatest
+    let history = match std::env::var("LAST_VALUE_DEPTH") {
+        Ok(depth) => depth.parse()?,
+        Err(_) => 10,
+    };
 
-        Promise::ok(())
+    let mut config = ZenohConfig::default();
+    if dry_run {
+        config.insert_json5("mode", r#""peer""#).unwrap();
+        config
+            .insert_json5("scouting/multicast/enabled", "false")
+            .unwrap();
+        config.insert_json5("listen/endpoints", "[]").unwrap();
+    } else {
+        config.insert_json5("mode", r#""router""#).unwrap();
     }
-}
-
-impl twist_service::Server for TwistZenohService {
-    fn do_twist(
-        &mut self,
-        params: twist_service::DoTwistParams,
-        _: twist_service::DoTwistResults,
-    ) -> capnp::capability::Promise<(), capnp::Error> {
-        let data = params.get().unwrap().get_data().unwrap();
-        dbg!("received: ", &data);
-        let linear = data.get_linear().unwrap();
-        let angular = data.get_angular().unwrap();
-
-        // Serialize twist
-        let twist_ser = Twist {
-            linear: Vector3 {
-                x: linear.get_x(),
-                y: linear.get_y(),
-                z: linear.get_z(),
-            },
-            angular: Vector3 {
-                x: angular.get_x(),
-                y: angular.get_y(),
-                z: angular.get_z(),
-            },
-        };
-        let encoded = cdr::serialize::<_, _, CdrLe>(&twist_ser, Infinite).unwrap();
-
-        println!(
-            "Publishing twist message to zenoh: x {} y {} z {} angular: x {} y {} z {}",
-            linear.get_x(),
-            linear.get_y(),
-            linear.get_z(),
-            angular.get_x(),
-            angular.get_y(),
-            angular.get_z()
-        );
 
-        let session = self.session.clone();
-        let topic = self.topic.clone();
-        tokio::spawn(async move {
-            let publisher = session.declare_publisher(&topic).await.unwrap();
-            match publisher.put(encoded).await {
-                Ok(_) => println!("Twist sent to zenoh on {} topic", topic),
-                Err(e) => {
-                    eprintln!("Failed to publish twist to zenoh: {}", e)
-                }
-            }
-        });
-
-        Promise::ok(())
-    }
-}
+    println!("Starting with zenoh config: {:?}", &config);
+    let session = zenoh::open(config).await.unwrap();
+    session.declare_publisher("rt/rosout").await.unwrap();
+    println!("Session: {:?}", &session);
 
-// Builder for creating the bootstrap service with configured publishers
-pub struct BootstrapServiceBuilder {
-    zenoh_session: zenoh::Session,
-    hello_topic: Option<String>,
-    twist_topic: Option<String>,
-}
+    try_init_log_from_env();
 
-impl BootstrapServiceBuilder {
-    pub fn new(zenoh_session: zenoh::Session) -> Self {
-        Self {
-            zenoh_session,
-            hello_topic: None,
-            twist_topic: None,
+    // Generic publishers, message services and action goals need the ROS message definitions.
+    // A ROS_MSG_DIR that fails to load is an error, a missing default msgs only disables them
+    let msg_dir = match std::env::var("ROS_MSG_DIR") {
+        Ok(dir) => Some(dir),
+        Err(_) if Path::new("msgs").is_dir() => Some("msgs".to_string()),
+        Err(_) => None,
+    };
+    let registry = match msg_dir {
+        Some(dir) => {
+            let registry = Arc::new(TypeRegistry::load_dir(&dir)?);
+            println!("Loaded {} ROS message types from {}", registry.len(), dir);
+            Some(registry)
         }
+        None => {
+            println!("No msgs directory, generic publishers, messages and actions are disabled");
+            None
+        }
+    };
+
+    // USE_SIM_TIME=true follows /clock, PUBLISH_CLOCK=<rate> publishes it for replays
+    let clock = match std::env::var("PUBLISH_CLOCK") {
+        Ok(rate) => ClockSource::Publish {
+            rate: rate.parse()?,
+        },
+        Err(_) if std::env::var("USE_SIM_TIME").is_ok_and(|v| v == "true" || v == "1") => {
+            ClockSource::Sim
+        }
+        Err(_) => ClockSource::Wall,
+    };
+
+    // Comma separated <key expression>=<policy> for messages put without subscribers, the
+    // first including a key applies and others are dropped. Motion commands are never buffered
+    let mut zenoh_sink = ZenohSink::new(session.clone()).with_history(history);
+    let buffering =
+        std::env::var("BUFFERING").unwrap_or_else(|_| "fleet/hello=fifo:100:60".to_string());
+    for entry in buffering
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+    {
+        let (key_expr, policy) = entry.split_once('=').ok_or_else(|| {
+            format!(
+                "Invalid BUFFERING entry {}, expected <key expression>=<policy>",
+                entry
+            )
+        })?;
+        let key_expr = OwnedKeyExpr::new(key_expr).map_err(|e| e.to_string())?;
+        zenoh_sink = zenoh_sink.with_buffering(key_expr, policy.parse::<Buffering>()?);
     }
 
-    pub fn with_hello_publisher(mut self, topic: impl Into<String>) -> Self {
-        self.hello_topic = Some(topic.into());
-        self
-    }
-
-    pub fn with_twist_publisher(mut self, topic: impl Into<String>) -> Self {
-        self.twist_topic = Some(topic.into());
-        self
-    }
-
-    pub async fn build(self) -> Result<BootstrapService, Box<dyn std::error::Error>> {
-        Ok(BootstrapService {
-            zenoh_session: self.zenoh_session,
-            hello_topic: self.hello_topic,
-            twist_topic: self.twist_topic,
-        })
+    // Create bootstrap service using builder pattern
+    let mut builder = BootstrapServiceBuilder::new(session.clone())
+        .with_clock(clock)
+        .with_sink(sink.open(zenoh_sink)?)
+        .with_hello_publisher("fleet/hello")
+        .with_twist_publisher("turtle1/cmd_vel")
+        .with_turtlesim_service()
+        .with_parameter_services()
+        .with_image_streams()
+        .with_tf_buffer()
+        .with_turtle_tf("world")
+        .with_dead_reckoning("{robot}/odom/dead_reckoning")
+        .with_status_monitor();
+    if let Some(registry) = registry {
+        builder = builder
+            .with_generic_publishers(registry.clone())
+            .with_message_services(registry.clone())
+            .with_generic_actions(registry);
     }
-}
-
-// Bootstrap service that provides access to both services
-pub struct BootstrapService {
-    zenoh_session: zenoh::Session,
-    hello_topic: Option<String>,
-    twist_topic: Option<String>,
-}
-
-impl bootstrap::Server for BootstrapService {
-    fn get_hello_service(
-        &mut self,
-        _params: bootstrap::GetHelloServiceParams,
-        mut results: bootstrap::GetHelloServiceResults,
-    ) -> capnp::capability::Promise<(), capnp::Error> {
-        if let Some(ref topic) = self.hello_topic {
-            let hello_service = capnp_rpc::new_client(HelloZenohService {
-                session: self.zenoh_session.clone(),
-                topic: topic.clone(),
-            });
-            results.get().set_service(hello_service);
-            Promise::ok(())
-        } else {
-            Promise::err(capnp::Error::failed(
-                "Hello publisher not configured".to_string(),
-            ))
-        }
+    // Comma separated <service>=<qos>, teleop commands overtake bulk traffic unless overridden
+    let service_qos =
+        std::env::var("SERVICE_QOS").unwrap_or_else(|_| "twist=1:express,hello=6".to_string());
+    for entry in service_qos
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+    {
+        let (service, qos) = entry.split_once('=').ok_or_else(|| {
+            format!(
+                "Invalid SERVICE_QOS entry {}, expected <service>=<qos>",
+                entry
+            )
+        })?;
+        builder = builder.with_service_qos(service, qos.parse::<Qos>()?);
     }
-
-    fn get_twist_service(
-        &mut self,
-        _params: bootstrap::GetTwistServiceParams,
-        mut results: bootstrap::GetTwistServiceResults,
-    ) -> capnp::capability::Promise<(), capnp::Error> {
-        if let Some(ref topic) = self.twist_topic {
-            let twist_service = capnp_rpc::new_client(TwistZenohService {
-                session: self.zenoh_session.clone(),
-                topic: topic.clone(),
-            });
-            results.get().set_service(twist_service);
-            Promise::ok(())
-        } else {
-            Promise::err(capnp::Error::failed(
-                "Twist publisher not configured".to_string(),
-            ))
+    // Comma separated robot names, e.g. STAMPED_TWIST_ROBOTS=robot1,robot2
+    if let Ok(robots) = std::env::var("STAMPED_TWIST_ROBOTS") {
+        for robot in robots.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            builder = builder.with_stamped_twist(robot);
         }
     }
-}
-
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut config = ZenohConfig::default();
-    config.insert_json5("mode", r#""router""#).unwrap();
-
-    println!("Starting with zenoh config: {:?}", &config);
-    let session = zenoh::open(config).await.unwrap();
-    session.declare_publisher("rt/rosout").await.unwrap();
-    println!("Session: {:?}", &session);
-
-    try_init_log_from_env();
-
-    let addr = "0.0.0.0:7000";
 
+    // `session` outlives the LocalSet, zenoh blocks when the last handle of a session is
+    // dropped and that is not allowed on a LocalSet
     tokio::task::LocalSet::new()
         .run_until(async move {
-            let listener = tokio::net::TcpListener::bind(&addr).await?;
-            println!("Listening on {}", &addr);
-
-            // Create bootstrap service using builder pattern
-            let bootstrap_service = BootstrapServiceBuilder::new(session.clone())
-                .with_hello_publisher("fleet/hello")
-                .with_twist_publisher("turtle1/cmd_vel")
-                .build()
-                .await?;
-
-            let bootstrap_client: schema_capnp::bootstrap::Client =
-                capnp_rpc::new_client(bootstrap_service);
-
-            println!("Cap n' Proto bootstrap client created");
-            loop {
-                let (stream, _) = listener.accept().await?;
-                stream.set_nodelay(true)?;
-                let (reader, writer) =
-                    tokio_util::compat::TokioAsyncReadCompatExt::compat(stream).split();
-                let network = twoparty::VatNetwork::new(
-                    futures::io::BufReader::new(reader),
-                    futures::io::BufWriter::new(writer),
-                    rpc_twoparty_capnp::Side::Server,
-                    Default::default(),
-                );
-
-                let rpc = RpcSystem::new(Box::new(network), Some(bootstrap_client.clone().client));
-
-                println!("RPC System created");
-                tokio::task::spawn_local(rpc);
-                println!("RPC System spawned");
-            }
+            let router = Router::serve(RouterConfig::new(builder)).await?;
+            tokio::signal::ctrl_c().await?;
+            router.shutdown().await;
+            Ok(())
         })
         .await
 }
@@ -0,0 +1,283 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+use cdr::{CdrLe, Infinite};
+use tokio::sync::watch;
+
+use crate::dynamic_cdr::{self, Value};
+use crate::projection::Projection;
+use crate::publisher::write_value;
+use crate::ros_idl::TypeRegistry;
+use crate::ros_types::{geometry_msgs, nav_msgs, sensor_msgs};
+use crate::schema_capnp::{
+    MessageType, message_listener, message_publisher, ros_message, subscription,
+    subscription_options,
+};
+use crate::sink::{Encoding, Message, MessageSink, Qos, Source};
+
+// Maps each MessageType onto its generated ROS type and RosMessage union member
+macro_rules! typed_messages {
+    ($($variant:ident($ty:ty, $ros_type:literal) => $init:ident),* $(,)?) => {
+        // Messages only live until they are encoded or forwarded, boxing Odometry is not worth it
+        #[allow(clippy::large_enum_variant)]
+        pub enum TypedMessage {
+            $($variant($ty)),*
+        }
+
+        // Fully qualified ROS type name of a message type
+        pub fn ros_type(message_type: MessageType) -> &'static str {
+            match message_type {
+                $(MessageType::$variant => $ros_type),*
+            }
+        }
+
+        impl TypedMessage {
+            pub fn read(reader: ros_message::Reader) -> Result<Self, capnp::Error> {
+                Ok(match reader.which()? {
+                    $(ros_message::Which::$variant(r) => Self::$variant(<$ty>::read(r?)?)),*
+                })
+            }
+
+            pub fn write(&self, builder: ros_message::Builder) {
+                match self {
+                    $(Self::$variant(m) => m.write(builder.$init())),*
+                }
+            }
+
+            pub fn message_type(&self) -> MessageType {
+                match self {
+                    $(Self::$variant(_) => MessageType::$variant),*
+                }
+            }
+
+            pub fn encode(&self) -> Result<Vec<u8>, cdr::Error> {
+                match self {
+                    $(Self::$variant(m) => cdr::serialize::<_, _, CdrLe>(m, Infinite)),*
+                }
+            }
+
+            pub fn decode(message_type: MessageType, data: &[u8]) -> Result<Self, cdr::Error> {
+                Ok(match message_type {
+                    $(MessageType::$variant => Self::$variant(cdr::deserialize(data)?)),*
+                })
+            }
+        }
+    };
+}
+
+typed_messages! {
+    Twist(geometry_msgs::Twist, "geometry_msgs/msg/Twist") => init_twist,
+    TwistStamped(geometry_msgs::TwistStamped, "geometry_msgs/msg/TwistStamped") => init_twist_stamped,
+    Point(geometry_msgs::Point, "geometry_msgs/msg/Point") => init_point,
+    Quaternion(geometry_msgs::Quaternion, "geometry_msgs/msg/Quaternion") => init_quaternion,
+    Pose(geometry_msgs::Pose, "geometry_msgs/msg/Pose") => init_pose,
+    PoseStamped(geometry_msgs::PoseStamped, "geometry_msgs/msg/PoseStamped") => init_pose_stamped,
+    Odometry(nav_msgs::Odometry, "nav_msgs/msg/Odometry") => init_odometry,
+    Path(nav_msgs::Path, "nav_msgs/msg/Path") => init_path,
+    LaserScan(sensor_msgs::LaserScan, "sensor_msgs/msg/LaserScan") => init_laser_scan,
+    Imu(sensor_msgs::Imu, "sensor_msgs/msg/Imu") => init_imu,
+    JointState(sensor_msgs::JointState, "sensor_msgs/msg/JointState") => init_joint_state,
+    BatteryState(sensor_msgs::BatteryState, "sensor_msgs/msg/BatteryState") => init_battery_state,
+}
+
+// Publishes messages of a single type, checked against the type requested by the client
+pub struct TypedSinkPublisher {
+    pub sink: Arc<dyn MessageSink>,
+    pub qos: Qos,
+    pub source: Arc<Source>,
+    pub topic: String,
+    pub message_type: MessageType,
+}
+
+impl message_publisher::Server for TypedSinkPublisher {
+    fn publish(
+        &mut self,
+        params: message_publisher::PublishParams,
+        _: message_publisher::PublishResults,
+    ) -> Promise<(), capnp::Error> {
+        let message = pry!(TypedMessage::read(pry!(pry!(params.get()).get_message())));
+        if message.message_type() != self.message_type {
+            return Promise::err(capnp::Error::failed(format!(
+                "Publisher on {} expects {:?}, got {:?}",
+                self.topic,
+                self.message_type,
+                message.message_type()
+            )));
+        }
+        let encoded = pry!(message.encode().map_err(|e| {
+            capnp::Error::failed(format!("Failed to encode {:?}: {}", self.message_type, e))
+        }));
+
+        Promise::from_future(self.sink.publish(Message {
+            key: self.topic.clone(),
+            payload: encoded,
+            encoding: Encoding::cdr(ros_type(self.message_type)),
+            qos: self.qos,
+            origin: self.source.origin(&self.topic),
+        }))
+    }
+}
+
+// Per-subscription downsampling and field projection
+#[derive(Default)]
+pub struct SubscriptionOptions {
+    pub min_interval: Option<Duration>,
+    pub projection: Option<Projection>,
+}
+
+// Spacing of deliveries for a client's maxRate in messages per second, 0 for no limit. Rates
+// so small that the spacing overflows a Duration are rejected with the negative ones
+pub fn min_interval(max_rate: f32) -> Result<Option<Duration>, capnp::Error> {
+    if max_rate == 0.0 {
+        return Ok(None);
+    }
+    match Duration::try_from_secs_f32(1.0 / max_rate) {
+        Ok(interval) if max_rate > 0.0 => Ok(Some(interval)),
+        _ => Err(capnp::Error::failed(format!(
+            "Invalid maxRate {}",
+            max_rate
+        ))),
+    }
+}
+
+impl SubscriptionOptions {
+    pub fn read(
+        reader: subscription_options::Reader,
+        registry: &TypeRegistry,
+        message_type: MessageType,
+    ) -> Result<Self, capnp::Error> {
+        let min_interval = min_interval(reader.get_max_rate())?;
+        let fields = reader
+            .get_fields()?
+            .iter()
+            .map(|field| Ok(field?.to_string()?))
+            .collect::<Result<Vec<_>, capnp::Error>>()?;
+        let projection = if fields.is_empty() {
+            None
+        } else {
+            Some(
+                Projection::new(registry, ros_type(message_type), &fields)
+                    .map_err(|e| capnp::Error::failed(e.to_string()))?,
+            )
+        };
+
+        Ok(Self {
+            min_interval,
+            projection,
+        })
+    }
+}
+
+// What a subscription forwards to its listener
+enum Delivery {
+    // Boxed, field deliveries would otherwise take the size of the largest message
+    Message(Box<TypedMessage>),
+    Fields(Vec<(String, Value)>),
+}
+
+impl Delivery {
+    async fn send(&self, listener: &message_listener::Client) -> Result<(), capnp::Error> {
+        match self {
+            Self::Message(message) => {
+                let mut request = listener.on_message_request();
+                message.write(request.get().init_message());
+                request.send().promise.await?;
+            }
+            Self::Fields(fields) => {
+                let mut request = listener.on_fields_request();
+                let mut list = request.get().init_fields(fields.len() as u32);
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    let mut field = list.reborrow().get(i as u32);
+                    field.set_name(name);
+                    write_value(value, field.init_value());
+                }
+                request.send().promise.await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Forwards decoded messages to a client listener, unsubscribes from zenoh when canceled or dropped
+pub struct TypedZenohSubscription {
+    subscriber: Option<zenoh::pubsub::Subscriber<()>>,
+}
+
+impl TypedZenohSubscription {
+    pub async fn declare(
+        session: &zenoh::Session,
+        topic: &str,
+        message_type: MessageType,
+        options: SubscriptionOptions,
+        registry: Arc<TypeRegistry>,
+        listener: message_listener::Client,
+    ) -> Result<Self, capnp::Error> {
+        // Only the latest message is kept, messages arriving while the previous one waits for
+        // the client's flow control window are dropped instead of queueing without bound
+        let (tx, mut rx) = watch::channel(None::<Arc<Delivery>>);
+        let last_delivery = Mutex::new(None::<Instant>);
+        let subscriber = session
+            .declare_subscriber(topic)
+            .callback(move |sample| {
+                // Drop messages arriving faster than the client asked for before decoding them
+                if let Some(interval) = options.min_interval {
+                    let mut last = last_delivery.lock().unwrap();
+                    let now = Instant::now();
+                    if last.is_some_and(|t| now.duration_since(t) < interval) {
+                        return;
+                    }
+                    *last = Some(now);
+                }
+
+                let payload = sample.payload().to_bytes();
+                let delivery = match &options.projection {
+                    None => TypedMessage::decode(message_type, &payload)
+                        .map(|message| Delivery::Message(Box::new(message)))
+                        .map_err(|e| e.to_string()),
+                    Some(projection) => {
+                        dynamic_cdr::decode(&registry, ros_type(message_type), &payload)
+                            .map(|value| Delivery::Fields(projection.apply(&value)))
+                            .map_err(|e| e.to_string())
+                    }
+                };
+                match delivery {
+                    Ok(delivery) => {
+                        tx.send_replace(Some(Arc::new(delivery)));
+                    }
+                    Err(e) => eprintln!("Failed to decode {:?}: {}", message_type, e),
+                }
+            })
+            .await
+            .map_err(|e| capnp::Error::failed(format!("Failed to subscribe {}: {}", topic, e)))?;
+
+        // The channel closes once the zenoh subscriber is dropped
+        tokio::task::spawn_local(async move {
+            while rx.changed().await.is_ok() {
+                let Some(delivery) = rx.borrow_and_update().clone() else {
+                    continue;
+                };
+                if let Err(e) = delivery.send(&listener).await {
+                    eprintln!("Failed to deliver message to client: {}", e);
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            subscriber: Some(subscriber),
+        })
+    }
+}
+
+impl subscription::Server for TypedZenohSubscription {
+    fn cancel(
+        &mut self,
+        _: subscription::CancelParams,
+        _: subscription::CancelResults,
+    ) -> Promise<(), capnp::Error> {
+        self.subscriber.take();
+        Promise::ok(())
+    }
+}
@@ -0,0 +1,30 @@
+// Options clients pass to typed subscriptions
+
+use std::time::Duration;
+
+use crate::messages::min_interval;
+
+#[test]
+fn max_rate_spaces_deliveries() {
+    assert_eq!(min_interval(0.0).unwrap(), None);
+    assert_eq!(
+        min_interval(10.0).unwrap(),
+        Some(Duration::from_secs_f32(0.1))
+    );
+}
+
+#[test]
+fn invalid_max_rates_are_rejected() {
+    for max_rate in [-1.0, f32::NAN, f32::NEG_INFINITY] {
+        assert!(min_interval(max_rate).is_err(), "{}", max_rate);
+    }
+}
+
+#[test]
+fn max_rates_overflowing_the_interval_are_rejected() {
+    // 1 / rate overflows a Duration, or f32 itself for subnormals
+    for max_rate in [1e-30, f32::MIN_POSITIVE, f32::from_bits(1)] {
+        let error = min_interval(max_rate).unwrap_err();
+        assert!(error.to_string().contains("Invalid maxRate"), "{}", error);
+    }
+}
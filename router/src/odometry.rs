@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use capnp::capability::Promise;
+
+use crate::clock::Clock;
+use crate::ros_types::{geometry_msgs, nav_msgs, std_msgs, turtlesim};
+use crate::schema_capnp::{dead_reckoning, odometry_estimate};
+use crate::sink::{Encoding, RouterPublisher};
+use crate::tf::from_nanos;
+
+// How often estimates are published
+const PUBLISH_PERIOD: Duration = Duration::from_millis(100);
+
+// Wraps an angle into [-pi, pi]
+fn normalize_angle(angle: f64) -> f64 {
+    let wrapped = (angle + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped == -PI { PI } else { wrapped }
+}
+
+// Planar pose of a unicycle, theta is the heading in radians
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Pose2D {
+    pub x: f64,
+    pub y: f64,
+    pub theta: f64,
+}
+
+impl Pose2D {
+    // Drives at constant velocities for `dt` seconds, following the exact arc
+    pub fn drive(&self, linear: f64, angular: f64, dt: f64) -> Self {
+        let theta = self.theta + angular * dt;
+        let (x, y) = if angular.abs() < 1e-9 {
+            (
+                self.x + linear * self.theta.cos() * dt,
+                self.y + linear * self.theta.sin() * dt,
+            )
+        } else {
+            let radius = linear / angular;
+            (
+                self.x + radius * (theta.sin() - self.theta.sin()),
+                self.y - radius * (theta.cos() - self.theta.cos()),
+            )
+        };
+        Self {
+            x,
+            y,
+            theta: normalize_angle(theta),
+        }
+    }
+}
+
+impl From<&turtlesim::Pose> for Pose2D {
+    fn from(pose: &turtlesim::Pose) -> Self {
+        Self {
+            x: pose.x as f64,
+            y: pose.y as f64,
+            theta: pose.theta as f64,
+        }
+    }
+}
+
+struct Command {
+    linear: f64,
+    angular: f64,
+    // Robots stop when no command arrives for a while, turtlesim after one second
+    until: i64,
+}
+
+// Dead reckoning of a single robot from the commands sent to it
+pub struct Estimate {
+    pose: Pose2D,
+    // Clock time of `pose` in nanoseconds
+    updated: i64,
+    command: Option<Command>,
+    // Latest pose reported by the robot itself, if it has a pose topic
+    true_pose: Option<Pose2D>,
+    commanded: bool,
+}
+
+// Difference between the estimate and the reported pose
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Drift {
+    pub position: f64,
+    pub heading: f64,
+}
+
+impl Estimate {
+    pub fn new(pose: Pose2D, now: i64) -> Self {
+        Self {
+            pose,
+            updated: now,
+            command: None,
+            true_pose: None,
+            commanded: false,
+        }
+    }
+
+    // The estimated pose and velocities at `now`
+    pub fn at(&self, now: i64) -> (Pose2D, f64, f64) {
+        let Some(command) = &self.command else {
+            return (self.pose, 0.0, 0.0);
+        };
+        let end = now.min(command.until).max(self.updated);
+        let pose = self.pose.drive(
+            command.linear,
+            command.angular,
+            (end - self.updated) as f64 / 1e9,
+        );
+        match now < command.until {
+            true => (pose, command.linear, command.angular),
+            false => (pose, 0.0, 0.0),
+        }
+    }
+
+    pub fn command(&mut self, linear: f64, angular: f64, now: i64, timeout: Duration) {
+        self.pose = self.at(now).0;
+        self.updated = now;
+        self.command = Some(Command {
+            linear,
+            angular,
+            until: now + timeout.as_nanos() as i64,
+        });
+        self.commanded = true;
+    }
+
+    pub fn observe(&mut self, pose: Pose2D) {
+        // Start from the true pose when it is known before the first command
+        if !self.commanded {
+            self.pose = pose;
+        }
+        self.true_pose = Some(pose);
+    }
+
+    pub fn drift(&self, now: i64) -> Option<Drift> {
+        let truth = self.true_pose?;
+        let (estimate, _, _) = self.at(now);
+        Some(Drift {
+            position: (estimate.x - truth.x).hypot(estimate.y - truth.y),
+            heading: normalize_angle(estimate.theta - truth.theta),
+        })
+    }
+
+    // Starts over from the reported pose, or the origin
+    pub fn reset(&mut self, now: i64) {
+        self.pose = self.true_pose.unwrap_or_default();
+        self.updated = now;
+        self.command = None;
+    }
+
+    pub fn odometry(&self, robot: &str, now: i64) -> nav_msgs::Odometry {
+        let (pose, linear, angular) = self.at(now);
+        let mut odometry = nav_msgs::Odometry {
+            header: std_msgs::Header {
+                stamp: from_nanos(now),
+                frame_id: "odom".to_string(),
+            },
+            child_frame_id: robot.to_string(),
+            ..Default::default()
+        };
+        odometry.pose.pose.position = geometry_msgs::Point {
+            x: pose.x,
+            y: pose.y,
+            z: 0.0,
+        };
+        odometry.pose.pose.orientation = geometry_msgs::Quaternion {
+            z: (pose.theta / 2.0).sin(),
+            w: (pose.theta / 2.0).cos(),
+            ..Default::default()
+        };
+        // Twist is in the child frame, the unicycle only moves forward and turns
+        odometry.twist.twist.linear.x = linear;
+        odometry.twist.twist.angular.z = angular;
+        odometry
+    }
+}
+
+// Integrates the twists sent through the router into odometry for every robot
+pub struct DeadReckoning {
+    robots: Mutex<HashMap<String, Estimate>>,
+    clock: Clock,
+    command_timeout: Duration,
+}
+
+impl DeadReckoning {
+    pub fn new(clock: Clock, command_timeout: Duration) -> Self {
+        Self {
+            robots: Mutex::new(HashMap::new()),
+            clock,
+            command_timeout,
+        }
+    }
+
+    pub fn command(&self, robot: &str, twist: &geometry_msgs::Twist) {
+        let now = self.clock.now();
+        self.robots
+            .lock()
+            .unwrap()
+            .entry(robot.to_string())
+            .or_insert_with(|| Estimate::new(Pose2D::default(), now))
+            .command(twist.linear.x, twist.angular.z, now, self.command_timeout);
+    }
+
+    fn observe(&self, robot: &str, pose: Pose2D) {
+        self.robots
+            .lock()
+            .unwrap()
+            .entry(robot.to_string())
+            .or_insert_with(|| Estimate::new(pose, self.clock.now()))
+            .observe(pose);
+    }
+}
+
+// Feeds reported turtle poses into the estimates and publishes odometry for every commanded
+// robot on `key`, where `{robot}` is replaced by the robot name
+pub async fn start(
+    session: &zenoh::Session,
+    dead_reckoning: Arc<DeadReckoning>,
+    key: String,
+    publisher: RouterPublisher,
+) -> Result<zenoh::pubsub::Subscriber<()>, capnp::Error> {
+    let sink = dead_reckoning.clone();
+    let subscriber = session
+        .declare_subscriber("*/pose")
+        .callback(move |sample| {
+            let Some(robot) = sample.key_expr().as_str().strip_suffix("/pose") else {
+                return;
+            };
+            if let Ok(pose) = cdr::deserialize::<turtlesim::Pose>(&sample.payload().to_bytes()) {
+                sink.observe(robot, Pose2D::from(&pose));
+            }
+        })
+        .await
+        .map_err(|e| capnp::Error::failed(format!("Failed to subscribe */pose: {}", e)))?;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PUBLISH_PERIOD);
+        loop {
+            interval.tick().await;
+            let now = dead_reckoning.clock.now();
+            let messages: Vec<_> = dead_reckoning
+                .robots
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, estimate)| estimate.commanded)
+                .map(|(robot, estimate)| {
+                    let odometry = estimate.odometry(robot, now);
+                    (
+                        key.replace("{robot}", robot),
+                        cdr::serialize::<_, _, cdr::CdrLe>(&odometry, cdr::Infinite).unwrap(),
+                    )
+                })
+                .collect();
+            for (key, encoded) in messages {
+                let published =
+                    publisher.publish(&key, encoded, Encoding::cdr("nav_msgs/msg/Odometry"));
+                if let Err(e) = published.await {
+                    eprintln!("Failed to publish odometry on {}: {}", key, e);
+                }
+            }
+        }
+    });
+    Ok(subscriber)
+}
+
+// RPC access to the estimate of one robot
+pub struct DeadReckoningService {
+    pub dead_reckoning: Arc<DeadReckoning>,
+    pub robot: String,
+}
+
+fn write_estimate(
+    estimate: &Estimate,
+    robot: &str,
+    now: i64,
+    mut builder: odometry_estimate::Builder,
+) {
+    estimate
+        .odometry(robot, now)
+        .write(builder.reborrow().init_odometry());
+    if let Some(drift) = estimate.drift(now) {
+        builder.set_has_drift(true);
+        builder.set_position_drift(drift.position);
+        builder.set_heading_drift(drift.heading);
+    }
+}
+
+impl dead_reckoning::Server for DeadReckoningService {
+    fn get_estimate(
+        &mut self,
+        _: dead_reckoning::GetEstimateParams,
+        mut results: dead_reckoning::GetEstimateResults,
+    ) -> Promise<(), capnp::Error> {
+        let robots = self.dead_reckoning.robots.lock().unwrap();
+        let Some(estimate) = robots.get(&self.robot) else {
+            return Promise::err(capnp::Error::failed(format!(
+                "No commands or pose seen for {} yet",
+                self.robot
+            )));
+        };
+        let now = self.dead_reckoning.clock.now();
+        write_estimate(estimate, &self.robot, now, results.get().init_estimate());
+        Promise::ok(())
+    }
+
+    fn reset(
+        &mut self,
+        _: dead_reckoning::ResetParams,
+        _: dead_reckoning::ResetResults,
+    ) -> Promise<(), capnp::Error> {
+        let now = self.dead_reckoning.clock.now();
+        if let Some(estimate) = self
+            .dead_reckoning
+            .robots
+            .lock()
+            .unwrap()
+            .get_mut(&self.robot)
+        {
+            estimate.reset(now);
+        }
+        Promise::ok(())
+    }
+}
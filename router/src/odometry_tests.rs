@@ -0,0 +1,99 @@
+// Dead reckoning of commanded twists with the unicycle model
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::time::Duration;
+
+use crate::odometry::{Estimate, Pose2D};
+
+const SECOND: i64 = 1_000_000_000;
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn assert_pose(actual: Pose2D, x: f64, y: f64, theta: f64) {
+    assert!(
+        (actual.x - x).abs() < 1e-9 && (actual.y - y).abs() < 1e-9,
+        "{:?} is not at ({}, {})",
+        actual,
+        x,
+        y
+    );
+    assert!(
+        (actual.theta - theta).abs() < 1e-9,
+        "{:?} != {}",
+        actual,
+        theta
+    );
+}
+
+#[test]
+fn drives_straight_along_heading() {
+    let start = Pose2D {
+        x: 1.0,
+        y: 2.0,
+        theta: FRAC_PI_2,
+    };
+    assert_pose(start.drive(2.0, 0.0, 1.5), 1.0, 5.0, FRAC_PI_2);
+}
+
+#[test]
+fn follows_arcs() {
+    // A quarter circle of radius 2 turning left from the origin
+    let quarter = Pose2D::default().drive(PI, FRAC_PI_2, 1.0);
+    assert_pose(quarter, 2.0, 2.0, FRAC_PI_2);
+
+    // A full circle comes back to the start
+    let full = Pose2D::default().drive(PI, FRAC_PI_2, 4.0);
+    assert_pose(full, 0.0, 0.0, 0.0);
+}
+
+#[test]
+fn commands_expire() {
+    let t0 = 1_000 * SECOND;
+    let mut estimate = Estimate::new(Pose2D::default(), t0);
+    estimate.command(1.0, 0.0, t0, TIMEOUT);
+
+    let (pose, linear, _) = estimate.at(t0 + SECOND / 2);
+    assert_pose(pose, 0.5, 0.0, 0.0);
+    assert_eq!(linear, 1.0);
+
+    // The robot stopped after the timeout
+    let (pose, linear, _) = estimate.at(t0 + 5 * SECOND);
+    assert_pose(pose, 1.0, 0.0, 0.0);
+    assert_eq!(linear, 0.0);
+
+    // A new command continues from where the previous one left the robot
+    let t1 = t0 + 5 * SECOND;
+    estimate.command(0.0, FRAC_PI_2, t1, TIMEOUT);
+    let (pose, _, angular) = estimate.at(t1 + SECOND / 2);
+    assert_pose(pose, 1.0, 0.0, FRAC_PI_2 / 2.0);
+    assert_eq!(angular, FRAC_PI_2);
+}
+
+#[test]
+fn reports_drift_from_true_pose() {
+    let t0 = 1_000 * SECOND;
+    let mut estimate = Estimate::new(Pose2D::default(), t0);
+    assert!(estimate.drift(t0).is_none());
+
+    // The first reported pose seeds the estimate
+    estimate.observe(Pose2D {
+        x: 5.0,
+        y: 5.0,
+        theta: 0.0,
+    });
+    estimate.command(1.0, 0.0, t0, TIMEOUT);
+    estimate.observe(Pose2D {
+        x: 5.5,
+        y: 4.0,
+        theta: -PI + 0.1,
+    });
+
+    let drift = estimate.drift(t0 + SECOND).unwrap();
+    assert!((drift.position - 0.5_f64.hypot(1.0)).abs() < 1e-9);
+    // Heading difference wraps around instead of being almost 2 pi
+    assert!((drift.heading - (PI - 0.1)).abs() < 1e-9);
+
+    estimate.reset(t0 + SECOND);
+    let drift = estimate.drift(t0 + 2 * SECOND).unwrap();
+    assert_eq!(drift.position, 0.0);
+    assert_eq!(drift.heading, 0.0);
+}
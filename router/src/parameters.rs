@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::query::call_service;
+use crate::schema_capnp::{parameter, parameter_value, set_parameter_result};
+
+// rcl_interfaces/msg/ParameterType values
+const PARAMETER_NOT_SET: u8 = 0;
+const PARAMETER_BOOL: u8 = 1;
+const PARAMETER_INTEGER: u8 = 2;
+const PARAMETER_DOUBLE: u8 = 3;
+const PARAMETER_STRING: u8 = 4;
+const PARAMETER_BYTE_ARRAY: u8 = 5;
+const PARAMETER_BOOL_ARRAY: u8 = 6;
+const PARAMETER_INTEGER_ARRAY: u8 = 7;
+const PARAMETER_DOUBLE_ARRAY: u8 = 8;
+const PARAMETER_STRING_ARRAY: u8 = 9;
+
+// rcl_interfaces/msg/ParameterValue, `kind` is the `type` field of the message
+#[derive(Deserialize, Serialize, PartialEq, Default)]
+pub struct ParameterValue {
+    kind: u8,
+    bool_value: bool,
+    integer_value: i64,
+    double_value: f64,
+    string_value: String,
+    byte_array_value: Vec<u8>,
+    bool_array_value: Vec<bool>,
+    integer_array_value: Vec<i64>,
+    double_array_value: Vec<f64>,
+    string_array_value: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct Parameter {
+    name: String,
+    value: ParameterValue,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct SetParametersResult {
+    successful: bool,
+    reason: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct GetParametersRequest {
+    names: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct GetParametersResponse {
+    values: Vec<ParameterValue>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct SetParametersRequest {
+    parameters: Vec<Parameter>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct SetParametersResponse {
+    results: Vec<SetParametersResult>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct ListParametersRequest {
+    prefixes: Vec<String>,
+    depth: u64,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct ListParametersResult {
+    pub names: Vec<String>,
+    pub prefixes: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct ListParametersResponse {
+    result: ListParametersResult,
+}
+
+impl ParameterValue {
+    pub fn read(reader: parameter_value::Reader) -> Result<Self, capnp::Error> {
+        use parameter_value::Which;
+
+        let mut value = Self::default();
+        match reader.which()? {
+            Which::NotSet(()) => value.kind = PARAMETER_NOT_SET,
+            Which::BoolValue(v) => {
+                value.kind = PARAMETER_BOOL;
+                value.bool_value = v;
+            }
+            Which::IntegerValue(v) => {
+                value.kind = PARAMETER_INTEGER;
+                value.integer_value = v;
+            }
+            Which::DoubleValue(v) => {
+                value.kind = PARAMETER_DOUBLE;
+                value.double_value = v;
+            }
+            Which::StringValue(v) => {
+                value.kind = PARAMETER_STRING;
+                value.string_value = v?.to_string()?;
+            }
+            Which::ByteArrayValue(v) => {
+                value.kind = PARAMETER_BYTE_ARRAY;
+                value.byte_array_value = v?.to_vec();
+            }
+            Which::BoolArrayValue(v) => {
+                value.kind = PARAMETER_BOOL_ARRAY;
+                value.bool_array_value = v?.iter().collect();
+            }
+            Which::IntegerArrayValue(v) => {
+                value.kind = PARAMETER_INTEGER_ARRAY;
+                value.integer_array_value = v?.iter().collect();
+            }
+            Which::DoubleArrayValue(v) => {
+                value.kind = PARAMETER_DOUBLE_ARRAY;
+                value.double_array_value = v?.iter().collect();
+            }
+            Which::StringArrayValue(v) => {
+                value.kind = PARAMETER_STRING_ARRAY;
+                value.string_array_value = v?
+                    .iter()
+                    .map(|s| Ok(s?.to_string()?))
+                    .collect::<Result<_, capnp::Error>>()?;
+            }
+        }
+        Ok(value)
+    }
+
+    pub fn write(&self, mut builder: parameter_value::Builder) {
+        match self.kind {
+            PARAMETER_BOOL => builder.set_bool_value(self.bool_value),
+            PARAMETER_INTEGER => builder.set_integer_value(self.integer_value),
+            PARAMETER_DOUBLE => builder.set_double_value(self.double_value),
+            PARAMETER_STRING => builder.set_string_value(&self.string_value),
+            PARAMETER_BYTE_ARRAY => builder.set_byte_array_value(&self.byte_array_value),
+            PARAMETER_BOOL_ARRAY => {
+                let mut list = builder.init_bool_array_value(self.bool_array_value.len() as u32);
+                for (i, v) in self.bool_array_value.iter().enumerate() {
+                    list.set(i as u32, *v);
+                }
+            }
+            PARAMETER_INTEGER_ARRAY => {
+                let mut list =
+                    builder.init_integer_array_value(self.integer_array_value.len() as u32);
+                for (i, v) in self.integer_array_value.iter().enumerate() {
+                    list.set(i as u32, *v);
+                }
+            }
+            PARAMETER_DOUBLE_ARRAY => {
+                let mut list =
+                    builder.init_double_array_value(self.double_array_value.len() as u32);
+                for (i, v) in self.double_array_value.iter().enumerate() {
+                    list.set(i as u32, *v);
+                }
+            }
+            PARAMETER_STRING_ARRAY => {
+                let mut list =
+                    builder.init_string_array_value(self.string_array_value.len() as u32);
+                for (i, v) in self.string_array_value.iter().enumerate() {
+                    list.set(i as u32, v);
+                }
+            }
+            _ => builder.set_not_set(()),
+        }
+    }
+}
+
+impl Parameter {
+    pub fn read(reader: parameter::Reader) -> Result<Self, capnp::Error> {
+        Ok(Self {
+            name: reader.get_name()?.to_string()?,
+            value: ParameterValue::read(reader.get_value()?)?,
+        })
+    }
+}
+
+impl SetParametersResult {
+    pub fn write(&self, mut builder: set_parameter_result::Builder) {
+        builder.set_successful(self.successful);
+        builder.set_reason(&self.reason);
+    }
+}
+
+// The parameter services of a node live under the node's fully qualified name
+fn service_key(node: &str, service: &str) -> Result<String, capnp::Error> {
+    let node = node.trim_matches('/');
+    if node.is_empty() {
+        return Err(capnp::Error::failed("Node name is empty".to_string()));
+    }
+    Ok(format!("{}/{}", node, service))
+}
+
+pub async fn get_parameters(
+    session: &zenoh::Session,
+    node: &str,
+    names: Vec<String>,
+) -> Result<Vec<ParameterValue>, capnp::Error> {
+    let key = service_key(node, "get_parameters")?;
+    let response: GetParametersResponse =
+        call_service(session, &key, &GetParametersRequest { names }).await?;
+    Ok(response.values)
+}
+
+pub async fn set_parameters(
+    session: &zenoh::Session,
+    node: &str,
+    parameters: Vec<Parameter>,
+) -> Result<Vec<SetParametersResult>, capnp::Error> {
+    let key = service_key(node, "set_parameters")?;
+    let response: SetParametersResponse =
+        call_service(session, &key, &SetParametersRequest { parameters }).await?;
+    Ok(response.results)
+}
+
+pub async fn list_parameters(
+    session: &zenoh::Session,
+    node: &str,
+) -> Result<ListParametersResult, capnp::Error> {
+    let key = service_key(node, "list_parameters")?;
+    // Depth 0 is DEPTH_RECURSIVE, no prefixes lists every parameter
+    let request = ListParametersRequest {
+        prefixes: Vec::new(),
+        depth: 0,
+    };
+    let response: ListParametersResponse = call_service(session, &key, &request).await?;
+    Ok(response.result)
+}
@@ -0,0 +1,129 @@
+use crate::dynamic_cdr::{CodecError, Value};
+use crate::ros_idl::{BaseType, Container, Primitive, TypeRegistry};
+
+// Summaries of numeric arrays, selected with a `min`, `max` or `mean` path suffix
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Summary {
+    Min,
+    Max,
+    Mean,
+}
+
+struct Selector {
+    // The path as requested by the client, also the name of the projected field
+    name: String,
+    fields: Vec<String>,
+    summary: Option<Summary>,
+}
+
+impl Selector {
+    fn resolve(registry: &TypeRegistry, ros_type: &str, path: &str) -> Result<Self, CodecError> {
+        let error = |reason: &str| {
+            CodecError(format!(
+                "Invalid field {} of {}: {}",
+                path, ros_type, reason
+            ))
+        };
+        let mut def = registry
+            .get(ros_type)
+            .ok_or_else(|| error("unknown type"))?;
+        let mut segments = path.split('.');
+        let mut fields = Vec::new();
+        while let Some(segment) = segments.next() {
+            let field = def
+                .fields
+                .iter()
+                .find(|f| f.name == segment)
+                .ok_or_else(|| error("no such field"))?;
+            fields.push(field.name.clone());
+
+            // Descend into nested messages, anything else ends the path
+            if let (BaseType::Message(name), Container::Single) = (&field.base, field.container) {
+                def = registry.get(name).ok_or_else(|| error("unknown type"))?;
+                continue;
+            }
+            let summary = match segments.next() {
+                None => None,
+                Some(_) if field.container == Container::Single || !is_numeric(&field.base) => {
+                    return Err(error("only numeric arrays can be summarized"));
+                }
+                Some("min") => Some(Summary::Min),
+                Some("max") => Some(Summary::Max),
+                Some("mean") => Some(Summary::Mean),
+                Some(_) => return Err(error("expected min, max or mean")),
+            };
+            if segments.next().is_some() {
+                return Err(error("path continues past the end of the message"));
+            }
+            return Ok(Self {
+                name: path.to_string(),
+                fields,
+                summary,
+            });
+        }
+        Ok(Self {
+            name: path.to_string(),
+            fields,
+            summary: None,
+        })
+    }
+
+    fn select(&self, message: &Value) -> Option<Value> {
+        let value = self
+            .fields
+            .iter()
+            .try_fold(message, |value, name| value.field(name))?;
+        let Some(summary) = self.summary else {
+            return Some(value.clone());
+        };
+        let Value::Array(elements) = value else {
+            return None;
+        };
+
+        // ROS uses NaN and infinity for readings without a valid measurement
+        let readings: Vec<f64> = elements
+            .iter()
+            .filter_map(Value::as_f64)
+            .filter(|v| v.is_finite())
+            .collect();
+        if readings.is_empty() {
+            return None;
+        }
+        Some(Value::Float64(match summary {
+            Summary::Min => readings.iter().copied().fold(f64::INFINITY, f64::min),
+            Summary::Max => readings.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Summary::Mean => readings.iter().sum::<f64>() / readings.len() as f64,
+        }))
+    }
+}
+
+fn is_numeric(base: &BaseType) -> bool {
+    matches!(base, BaseType::Primitive(p) if !matches!(p, Primitive::Bool | Primitive::String))
+}
+
+// Selects fields of a message by dotted path, e.g. `header.stamp` or `ranges.max`
+pub struct Projection {
+    selectors: Vec<Selector>,
+}
+
+impl Projection {
+    pub fn new(
+        registry: &TypeRegistry,
+        ros_type: &str,
+        paths: &[String],
+    ) -> Result<Self, CodecError> {
+        let selectors = paths
+            .iter()
+            .map(|path| Selector::resolve(registry, ros_type, path))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { selectors })
+    }
+
+    // Summaries without any valid reading are left out
+    pub fn apply(&self, message: &Value) -> Vec<(String, Value)> {
+        self.selectors
+            .iter()
+            .filter_map(|selector| Some((selector.name.clone(), selector.select(message)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+
+use crate::dynamic_cdr::{self, Value};
+use crate::ros_idl::TypeRegistry;
+use crate::schema_capnp::{generic_publisher, ros_value};
+use crate::sink::{Encoding, Message, MessageSink, Qos, Source};
+
+// Publishes messages of any type known to the registry, encoded to CDR from their
+// dynamic Cap'n Proto or JSON representation
+pub struct GenericSinkPublisher {
+    pub sink: Arc<dyn MessageSink>,
+    pub qos: Qos,
+    pub source: Arc<Source>,
+    pub topic: String,
+    pub ros_type: String,
+    pub registry: Arc<TypeRegistry>,
+}
+
+pub fn read_value(reader: ros_value::Reader) -> Result<Value, capnp::Error> {
+    use ros_value::Which;
+
+    Ok(match reader.which()? {
+        Which::Bool(v) => Value::Bool(v),
+        Which::Int8(v) => Value::Int8(v),
+        Which::Uint8(v) => Value::UInt8(v),
+        Which::Int16(v) => Value::Int16(v),
+        Which::Uint16(v) => Value::UInt16(v),
+        Which::Int32(v) => Value::Int32(v),
+        Which::Uint32(v) => Value::UInt32(v),
+        Which::Int64(v) => Value::Int64(v),
+        Which::Uint64(v) => Value::UInt64(v),
+        Which::Float32(v) => Value::Float32(v),
+        Which::Float64(v) => Value::Float64(v),
+        Which::String(v) => Value::String(v?.to_string()?),
+        Which::Array(v) => Value::Array(v?.iter().map(read_value).collect::<Result<_, _>>()?),
+        Which::Message(v) => Value::Message(
+            v?.iter()
+                .map(|field| {
+                    Ok((
+                        field.get_name()?.to_string()?,
+                        read_value(field.get_value()?)?,
+                    ))
+                })
+                .collect::<Result<_, capnp::Error>>()?,
+        ),
+    })
+}
+
+pub fn write_value(value: &Value, mut builder: ros_value::Builder) {
+    match value {
+        Value::Bool(v) => builder.set_bool(*v),
+        Value::Int8(v) => builder.set_int8(*v),
+        Value::UInt8(v) => builder.set_uint8(*v),
+        Value::Int16(v) => builder.set_int16(*v),
+        Value::UInt16(v) => builder.set_uint16(*v),
+        Value::Int32(v) => builder.set_int32(*v),
+        Value::UInt32(v) => builder.set_uint32(*v),
+        Value::Int64(v) => builder.set_int64(*v),
+        Value::UInt64(v) => builder.set_uint64(*v),
+        Value::Float32(v) => builder.set_float32(*v),
+        Value::Float64(v) => builder.set_float64(*v),
+        Value::String(v) => builder.set_string(v),
+        Value::Array(elements) => {
+            let mut list = builder.init_array(elements.len() as u32);
+            for (i, element) in elements.iter().enumerate() {
+                write_value(element, list.reborrow().get(i as u32));
+            }
+        }
+        Value::Message(fields) => {
+            let mut list = builder.init_message(fields.len() as u32);
+            for (i, (name, value)) in fields.iter().enumerate() {
+                let mut field = list.reborrow().get(i as u32);
+                field.set_name(name);
+                write_value(value, field.init_value());
+            }
+        }
+    }
+}
+
+impl GenericSinkPublisher {
+    fn put(&self, value: &Value) -> Promise<(), capnp::Error> {
+        let encoded = pry!(
+            dynamic_cdr::encode(&self.registry, &self.ros_type, value)
+                .map_err(|e| capnp::Error::failed(format!("Invalid {}: {}", self.ros_type, e)))
+        );
+
+        Promise::from_future(self.sink.publish(Message {
+            key: self.topic.clone(),
+            payload: encoded,
+            encoding: Encoding::cdr(self.ros_type.clone()),
+            qos: self.qos,
+            origin: self.source.origin(&self.topic),
+        }))
+    }
+}
+
+impl generic_publisher::Server for GenericSinkPublisher {
+    fn publish(
+        &mut self,
+        params: generic_publisher::PublishParams,
+        _: generic_publisher::PublishResults,
+    ) -> Promise<(), capnp::Error> {
+        let value = pry!(read_value(pry!(pry!(params.get()).get_message())));
+        self.put(&value)
+    }
+
+    fn publish_json(
+        &mut self,
+        params: generic_publisher::PublishJsonParams,
+        _: generic_publisher::PublishJsonResults,
+    ) -> Promise<(), capnp::Error> {
+        let json = pry!(pry!(pry!(params.get()).get_json()).to_str());
+        let json: serde_json::Value = pry!(
+            serde_json::from_str(json)
+                .map_err(|e| capnp::Error::failed(format!("Invalid JSON: {}", e)))
+        );
+        let value = pry!(
+            dynamic_cdr::from_json(&self.registry, &self.ros_type, &json)
+                .map_err(|e| capnp::Error::failed(format!("Invalid {}: {}", self.ros_type, e)))
+        );
+        self.put(&value)
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use cdr::{CdrLe, Infinite};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// How long to wait for a ROS service server to answer through the bridge. Wall time whatever
+// the router's clock, Zenoh runs the query timeout and the bridge answers in wall time, also
+// while a simulation is paused or runs faster than real time
+pub const SERVICE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ROS 2 represents empty requests/responses with a single placeholder byte
+#[derive(Deserialize, Serialize, PartialEq, Default)]
+pub struct Empty {
+    structure_needs_at_least_one_member: u8,
+}
+
+// Calls a ROS service exposed by the zenoh-bridge-ros2dds as a Zenoh queryable.
+// The request and the reply are both CDR encoded with the encapsulation header.
+pub async fn call_service<Req, Res>(
+    session: &zenoh::Session,
+    key: &str,
+    request: &Req,
+) -> Result<Res, capnp::Error>
+where
+    Req: Serialize,
+    Res: DeserializeOwned,
+{
+    call_service_with_timeout(session, key, request, SERVICE_TIMEOUT).await
+}
+
+pub async fn call_service_with_timeout<Req, Res>(
+    session: &zenoh::Session,
+    key: &str,
+    request: &Req,
+    timeout: Duration,
+) -> Result<Res, capnp::Error>
+where
+    Req: Serialize,
+    Res: DeserializeOwned,
+{
+    let encoded = cdr::serialize::<_, _, CdrLe>(request, Infinite)
+        .map_err(|e| capnp::Error::failed(format!("Failed to encode {} request: {}", key, e)))?;
+    let response = query_service(session, key, encoded, timeout).await?;
+    cdr::deserialize::<Res>(&response)
+        .map_err(|e| capnp::Error::failed(format!("Failed to decode {} response: {}", key, e)))
+}
+
+// Calls a service with an already encoded request and returns the encoded response, for
+// types only known at runtime
+pub async fn query_service(
+    session: &zenoh::Session,
+    key: &str,
+    request: Vec<u8>,
+    timeout: Duration,
+) -> Result<Vec<u8>, capnp::Error> {
+    let replies = session
+        .get(key)
+        .payload(request)
+        .encoding(zenoh::bytes::Encoding::APPLICATION_CDR)
+        .timeout(timeout)
+        .await
+        .map_err(|e| capnp::Error::failed(format!("Failed to query {}: {}", key, e)))?;
+
+    match replies.recv_async().await {
+        Ok(reply) => match reply.result() {
+            Ok(sample) => Ok(sample.payload().to_bytes().into_owned()),
+            Err(err) => Err(capnp::Error::failed(format!(
+                "Service {} replied with an error: {}",
+                key,
+                err.payload().try_to_string().unwrap_or_default()
+            ))),
+        },
+        Err(_) => Err(capnp::Error::failed(format!(
+            "No reply from service {} (is the ROS node running?)",
+            key
+        ))),
+    }
+}
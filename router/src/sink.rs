@@ -0,0 +1,735 @@
+// Where the router's services put the messages they publish. Zenoh in production, or a
+// recorder, stdout or a file to run without a network
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use zenoh::Wait;
+use zenoh::key_expr::{OwnedKeyExpr, keyexpr};
+use zenoh::qos::{CongestionControl, Priority, Reliability};
+use zenoh::query::{Query, Queryable};
+use zenoh::time::Timestamp;
+use zenoh_ext::{AdvancedPublisher, AdvancedPublisherBuilderExt, CacheConfig, MissDetectionConfig};
+
+use crate::clock::Clock;
+
+// Zenoh has no encoding for Cap'n Proto, this one goes out as its schema
+const CAPNP_PACKED: &str = "application/capnp-packed";
+
+// How a payload is encoded, sent along so consumers do not have to guess
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    // CDR of a ROS type, e.g. geometry_msgs/msg/Twist
+    Cdr(String),
+    CapnpPacked,
+    Json,
+    Text,
+}
+
+impl Encoding {
+    pub fn cdr(ros_type: impl Into<String>) -> Self {
+        Encoding::Cdr(ros_type.into())
+    }
+
+    // The encodings the router puts, None for anything else
+    pub fn from_zenoh(encoding: &zenoh::bytes::Encoding) -> Option<Self> {
+        let encoding = encoding.to_string();
+        let (id, schema) = encoding.split_once(';').unwrap_or((&encoding, ""));
+        match (id, schema) {
+            ("application/cdr", ros_type) if !ros_type.is_empty() => Some(Encoding::cdr(ros_type)),
+            ("zenoh/bytes", CAPNP_PACKED) => Some(Encoding::CapnpPacked),
+            ("application/json", _) => Some(Encoding::Json),
+            ("text/plain", _) => Some(Encoding::Text),
+            _ => None,
+        }
+    }
+}
+
+impl From<Encoding> for zenoh::bytes::Encoding {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Cdr(ros_type) => {
+                zenoh::bytes::Encoding::APPLICATION_CDR.with_schema(ros_type)
+            }
+            Encoding::CapnpPacked => zenoh::bytes::Encoding::from(CAPNP_PACKED),
+            Encoding::Json => zenoh::bytes::Encoding::APPLICATION_JSON,
+            Encoding::Text => zenoh::bytes::Encoding::TEXT_PLAIN,
+        }
+    }
+}
+
+// As Zenoh renders it, e.g. "application/cdr;geometry_msgs/msg/Twist"
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        zenoh::bytes::Encoding::from(self.clone()).fmt(f)
+    }
+}
+
+// Delivery settings of a message, the defaults are Zenoh's
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Qos {
+    pub priority: Priority,
+    pub congestion_control: CongestionControl,
+    pub express: bool,
+    pub reliability: Reliability,
+}
+
+// Written like the bridge's pub_priorities, a priority from 1 (highest) to 7 followed by
+// flags, e.g. "1:express:block" or "5:best_effort"
+impl FromStr for Qos {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let priority = parts.next().unwrap_or_default();
+        let priority = priority
+            .parse::<u8>()
+            .ok()
+            .and_then(|priority| Priority::try_from(priority).ok())
+            .ok_or_else(|| format!("Invalid priority {}, expected 1 to 7", priority))?;
+        let mut qos = Qos {
+            priority,
+            ..Qos::default()
+        };
+        for flag in parts {
+            match flag {
+                "express" => qos.express = true,
+                "block" => qos.congestion_control = CongestionControl::Block,
+                "drop" => qos.congestion_control = CongestionControl::Drop,
+                "reliable" => qos.reliability = Reliability::Reliable,
+                "best_effort" => qos.reliability = Reliability::BestEffort,
+                _ => {
+                    return Err(format!(
+                        "Unknown QoS flag {}, expected express, block, drop, reliable or \
+                         best_effort",
+                        flag
+                    ));
+                }
+            }
+        }
+        Ok(qos)
+    }
+}
+
+// Motion commands, never buffered as a late one would move the robot on stale intent
+const MOTION_TYPES: [&str; 2] = ["geometry_msgs/msg/Twist", "geometry_msgs/msg/TwistStamped"];
+
+// What happens to messages put while no subscriber matches their key, buffered ones are put
+// once one does
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Buffering {
+    #[default]
+    Drop,
+    // Only the last message, whatever its age
+    Latest,
+    // Up to `capacity` messages younger than `ttl`, the oldest dropped first
+    Fifo {
+        capacity: usize,
+        ttl: Duration,
+    },
+}
+
+// "drop", "latest" or "fifo:<capacity>:<ttl in seconds>", e.g. "fifo:100:60"
+impl FromStr for Buffering {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid buffering {}, expected drop, latest or fifo:<capacity>:<ttl seconds>",
+                s
+            )
+        };
+        match s.split(':').collect::<Vec<_>>()[..] {
+            ["drop"] => Ok(Buffering::Drop),
+            ["latest"] => Ok(Buffering::Latest),
+            ["fifo", capacity, ttl] => {
+                let capacity = capacity.parse().map_err(|_| invalid())?;
+                let ttl = ttl
+                    .parse()
+                    .ok()
+                    .and_then(|ttl| Duration::try_from_secs_f64(ttl).ok())
+                    .ok_or_else(invalid)?;
+                Ok(Buffering::Fifo { capacity, ttl })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+// Which client a message came from and when the router got it
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Origin {
+    pub client_id: String,
+    // Counts the messages of a client per key from 0, gaps mean drops
+    pub sequence: u64,
+    // Time the RPC call arrived on the router's clock, in nanoseconds since its epoch
+    pub received_at: i64,
+}
+
+// An RPC connection, numbering what it publishes
+pub struct Source {
+    id: String,
+    clock: Clock,
+    sequences: Mutex<HashMap<String, u64>>,
+}
+
+impl Source {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            clock: Clock::wall(),
+            sequences: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Stamps calls with `clock`, e.g. sim time, instead of wall time
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    // Called as the call publishing on `key` arrives
+    pub fn origin(&self, key: &str) -> Origin {
+        let mut sequences = self.sequences.lock().unwrap();
+        let next = sequences.entry(key.to_string()).or_default();
+        let sequence = *next;
+        *next += 1;
+        Origin {
+            client_id: self.id.clone(),
+            sequence,
+            received_at: self.clock.now(),
+        }
+    }
+}
+
+// Attached as JSON to every sample the router puts on Zenoh, the timestamp is the router's
+// HLC timestamp of the sample, e.g. "7386690599959157260/33a34a3b2e8b4a1b"
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Attachment {
+    #[serde(flatten)]
+    pub origin: Origin,
+    pub timestamp: String,
+}
+
+impl Attachment {
+    pub fn encode(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Message {
+    pub key: String,
+    pub payload: Vec<u8>,
+    pub encoding: Encoding,
+    pub qos: Qos,
+    pub origin: Origin,
+}
+
+// Publishes the messages the router generates itself, e.g. tf or odometry, through the sink
+// like the services do
+#[derive(Clone)]
+pub struct RouterPublisher {
+    pub sink: Arc<dyn MessageSink>,
+    pub qos: Qos,
+    pub source: Arc<Source>,
+}
+
+impl RouterPublisher {
+    pub fn publish(
+        &self,
+        key: &str,
+        payload: Vec<u8>,
+        encoding: Encoding,
+    ) -> BoxFuture<'static, Result<(), capnp::Error>> {
+        self.sink.publish(Message {
+            key: key.to_string(),
+            payload,
+            encoding,
+            qos: self.qos,
+            origin: self.source.origin(key),
+        })
+    }
+}
+
+// A message as it was put, with the timestamp it went out with
+#[derive(Clone, PartialEq, Debug)]
+pub struct CachedSample {
+    pub message: Message,
+    pub timestamp: Timestamp,
+}
+
+impl CachedSample {
+    pub fn attachment(&self) -> Attachment {
+        Attachment {
+            origin: self.message.origin.clone(),
+            timestamp: self.timestamp.to_string(),
+        }
+    }
+}
+
+pub trait MessageSink: Send + Sync {
+    // The message is taken when this is called, the future resolves once it is delivered
+    fn publish(&self, message: Message) -> BoxFuture<'static, Result<(), capnp::Error>>;
+
+    // Last samples put on `key`, oldest first. None when the sink keeps no history
+    fn latest(&self, _key: &str) -> Option<Vec<CachedSample>> {
+        None
+    }
+}
+
+// Samples kept per key, shared with the queryables answering from it
+#[derive(Default)]
+struct LastValues {
+    depth: usize,
+    samples: Mutex<HashMap<String, VecDeque<CachedSample>>>,
+}
+
+impl LastValues {
+    // Called once a sample was put, buffered ones are kept when they are replayed
+    fn push(&self, sample: CachedSample) {
+        if self.depth == 0 {
+            return;
+        }
+        let mut samples = self.samples.lock().unwrap();
+        let kept = samples.entry(sample.message.key.clone()).or_default();
+        kept.push_back(sample);
+        while kept.len() > self.depth {
+            kept.pop_front();
+        }
+    }
+
+    fn get(&self, key: &str) -> Vec<CachedSample> {
+        let samples = self.samples.lock().unwrap();
+        samples
+            .get(key)
+            .map(|kept| kept.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // `key` is the one the queryable was declared on, the query's may be a wildcard
+    fn reply(&self, key: &str, query: &Query) {
+        for sample in self.get(key) {
+            let attachment = sample.attachment().encode();
+            let reply = query
+                .reply(key, sample.message.payload)
+                .encoding(sample.message.encoding)
+                .timestamp(sample.timestamp)
+                .attachment(attachment)
+                .wait();
+            if let Err(e) = reply {
+                eprintln!("Failed to reply to {} on {}: {}", query.key_expr(), key, e);
+            }
+        }
+    }
+}
+
+fn put(
+    publisher: &AdvancedPublisher<'static>,
+    sample: &CachedSample,
+) -> impl Future<Output = zenoh::Result<()>> {
+    publisher
+        .put(sample.message.payload.clone())
+        .encoding(sample.message.encoding.clone())
+        .timestamp(sample.timestamp)
+        .attachment(sample.attachment().encode())
+        .into_future()
+}
+
+// A message waiting for a subscriber, put with the timestamp it was first meant to go out with
+struct Pending {
+    sample: CachedSample,
+    expires: Option<Instant>,
+}
+
+struct Declaration {
+    qos: Qos,
+    publisher: AdvancedPublisher<'static>,
+    // Replays what was buffered when the publisher matches a subscriber
+    watcher: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for Declaration {
+    fn drop(&mut self) {
+        self.watcher.abort();
+    }
+}
+
+// What is declared and buffered for one key. Each key has its own lock, so a put that blocks
+// or a long replay only holds up later messages on the same key
+#[derive(Default)]
+pub(crate) struct KeyState {
+    declaration: Option<Declaration>,
+    queryable: Option<Queryable<()>>,
+    pending: VecDeque<Pending>,
+}
+
+pub(crate) type SharedKeyState = Arc<tokio::sync::Mutex<KeyState>>;
+
+impl KeyState {
+    fn buffer(&mut self, buffering: Buffering, sample: CachedSample) {
+        let pending = &mut self.pending;
+        match buffering {
+            Buffering::Drop => {}
+            Buffering::Latest => {
+                pending.clear();
+                pending.push_back(Pending {
+                    sample,
+                    expires: None,
+                });
+            }
+            Buffering::Fifo { capacity, ttl } => {
+                let now = Instant::now();
+                pending.retain(|p| p.expires.is_none_or(|expires| expires > now));
+                pending.push_back(Pending {
+                    sample,
+                    expires: Some(now + ttl),
+                });
+                while pending.len() > capacity {
+                    pending.pop_front();
+                }
+            }
+        }
+    }
+
+    async fn replay(&mut self, last_values: &LastValues) -> zenoh::Result<()> {
+        match &self.declaration {
+            Some(declaration) => {
+                replay(&declaration.publisher, &mut self.pending, last_values).await
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+async fn replay(
+    publisher: &AdvancedPublisher<'static>,
+    pending: &mut VecDeque<Pending>,
+    last_values: &LastValues,
+) -> zenoh::Result<()> {
+    let now = Instant::now();
+    for pending in std::mem::take(pending) {
+        if pending.expires.is_none_or(|expires| expires > now) {
+            put(publisher, &pending.sample).await?;
+            last_values.push(pending.sample);
+        }
+    }
+    Ok(())
+}
+
+// Waits for `publisher` to match subscribers and puts what was buffered for `key` meanwhile
+fn watch(
+    publisher: &AdvancedPublisher<'static>,
+    key: String,
+    state: Weak<tokio::sync::Mutex<KeyState>>,
+    last_values: Arc<LastValues>,
+) -> impl Future<Output = zenoh::Result<tokio::task::JoinHandle<()>>> {
+    let listener = publisher.matching_listener().into_future();
+    async move {
+        let listener = listener.await?;
+        Ok(tokio::spawn(async move {
+            while let Ok(status) = listener.recv_async().await {
+                if !status.matching() {
+                    continue;
+                }
+                let Some(state) = state.upgrade() else {
+                    break;
+                };
+                let replayed = state.lock().await.replay(&last_values).await;
+                if let Err(e) = replayed {
+                    eprintln!("Failed to replay buffered messages on {}: {}", key, e);
+                }
+            }
+        }))
+    }
+}
+
+// Declares a publisher per key on first use and keeps it, so its QoS applies to every put.
+// The last samples of each key are cached, for advanced subscribers recovering history and for
+// plain gets on the key, e.g. `z_get -s turtle1/cmd_vel`. Messages without subscribers are
+// buffered according to the first policy whose key expression includes their key, and dropped
+// when there is none
+pub struct ZenohSink {
+    session: zenoh::Session,
+    keys: Mutex<HashMap<String, SharedKeyState>>,
+    last_values: Arc<LastValues>,
+    buffering: Arc<Vec<(OwnedKeyExpr, Buffering)>>,
+}
+
+impl ZenohSink {
+    pub fn new(session: zenoh::Session) -> Self {
+        Self {
+            session,
+            keys: Default::default(),
+            last_values: Arc::new(LastValues {
+                depth: 1,
+                ..Default::default()
+            }),
+            buffering: Default::default(),
+        }
+    }
+
+    // Policy for the keys `key_expr` includes, e.g. "fleet/**"
+    pub fn with_buffering(mut self, key_expr: OwnedKeyExpr, buffering: Buffering) -> Self {
+        Arc::make_mut(&mut self.buffering).push((key_expr, buffering));
+        self
+    }
+
+    fn buffering(&self, message: &Message) -> Buffering {
+        if matches!(&message.encoding, Encoding::Cdr(ros_type) if MOTION_TYPES.contains(&ros_type.as_str()))
+        {
+            return Buffering::Drop;
+        }
+        let Ok(key) = keyexpr::new(&message.key) else {
+            return Buffering::Drop;
+        };
+        self.buffering
+            .iter()
+            .find(|(key_expr, _)| key_expr.includes(key))
+            .map(|(_, buffering)| *buffering)
+            .unwrap_or_default()
+    }
+
+    // Only held to look the key up, never across an await
+    pub(crate) fn key_state(&self, key: &str) -> SharedKeyState {
+        self.keys
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone()
+    }
+
+    // Samples kept per key, 0 keeps none and declares no queryables
+    pub fn with_history(mut self, depth: usize) -> Self {
+        self.last_values = Arc::new(LastValues {
+            depth,
+            ..Default::default()
+        });
+        self
+    }
+}
+
+impl MessageSink for ZenohSink {
+    fn publish(&self, message: Message) -> BoxFuture<'static, Result<(), capnp::Error>> {
+        let session = self.session.clone();
+        let shared = self.key_state(&message.key);
+        let last_values = self.last_values.clone();
+        let buffering = self.buffering(&message);
+        async move {
+            let key = message.key.clone();
+            let failed = |e: zenoh::Error| {
+                capnp::Error::failed(format!("Failed to publish to zenoh on {}: {}", key, e))
+            };
+            let mut state = shared.lock().await;
+            if last_values.depth > 0 && state.queryable.is_none() {
+                let replies = last_values.clone();
+                let declared_key = key.clone();
+                let queryable = session
+                    .declare_queryable(key.clone())
+                    .callback(move |query| replies.reply(&declared_key, &query))
+                    .await
+                    .map_err(failed)?;
+                state.queryable = Some(queryable);
+            }
+            let state = &mut *state;
+            let declaration = match &mut state.declaration {
+                Some(declaration) if declaration.qos == message.qos => declaration,
+                // Publisher QoS is fixed at declaration, a change needs a new one
+                slot => {
+                    let builder = session
+                        .declare_publisher(key.clone())
+                        .priority(message.qos.priority)
+                        .congestion_control(message.qos.congestion_control)
+                        .express(message.qos.express)
+                        .reliability(message.qos.reliability)
+                        .sample_miss_detection(MissDetectionConfig::default())
+                        .publisher_detection();
+                    let builder = match last_values.depth {
+                        0 => builder,
+                        depth => builder.cache(CacheConfig::default().max_samples(depth)),
+                    };
+                    let publisher = builder.await.map_err(failed)?;
+                    let watcher = watch(
+                        &publisher,
+                        key.clone(),
+                        Arc::downgrade(&shared),
+                        last_values.clone(),
+                    )
+                    .await
+                    .map_err(failed)?;
+                    slot.insert(Declaration {
+                        qos: message.qos,
+                        publisher,
+                        watcher,
+                    })
+                }
+            };
+            let sample = CachedSample {
+                message,
+                timestamp: session.new_timestamp(),
+            };
+            let matching = match buffering {
+                // Without a buffer there is nothing to wait for
+                Buffering::Drop => true,
+                _ => declaration
+                    .publisher
+                    .matching_status()
+                    .await
+                    .map_err(failed)?
+                    .matching(),
+            };
+            if matching {
+                // Buffered messages go first, the watcher may not have been told yet
+                replay(&declaration.publisher, &mut state.pending, &last_values)
+                    .await
+                    .map_err(failed)?;
+                put(&declaration.publisher, &sample).await.map_err(failed)?;
+                last_values.push(sample);
+            } else {
+                state.buffer(buffering, sample);
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn latest(&self, key: &str) -> Option<Vec<CachedSample>> {
+        (self.last_values.depth > 0).then(|| self.last_values.get(key))
+    }
+}
+
+// Keeps every message in memory, for tests
+#[derive(Default)]
+pub struct RecordingSink {
+    messages: Mutex<Vec<Message>>,
+}
+
+impl RecordingSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Removes and returns what was published so far
+    pub fn take(&self) -> Vec<Message> {
+        std::mem::take(&mut self.messages.lock().unwrap())
+    }
+}
+
+impl MessageSink for RecordingSink {
+    fn publish(&self, message: Message) -> BoxFuture<'static, Result<(), capnp::Error>> {
+        self.messages.lock().unwrap().push(message);
+        futures::future::ok(()).boxed()
+    }
+}
+
+// Writes a JSON object per message and line. JSON payloads are embedded as they are, others
+// as hex
+pub struct JsonLinesSink<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+fn json_line(message: &Message) -> serde_json::Value {
+    let payload = match message.encoding {
+        Encoding::Json => serde_json::from_slice(&message.payload).ok(),
+        _ => None,
+    };
+    let payload = payload.unwrap_or_else(|| {
+        let hex: String = message
+            .payload
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        serde_json::Value::String(hex)
+    });
+    serde_json::json!({
+        "key": message.key,
+        "encoding": message.encoding.to_string(),
+        "priority": format!("{:?}", message.qos.priority),
+        "congestionControl": format!("{:?}", message.qos.congestion_control),
+        "express": message.qos.express,
+        "reliability": format!("{:?}", message.qos.reliability),
+        "origin": message.origin,
+        "payload": payload,
+    })
+}
+
+impl<W: Write + Send> MessageSink for JsonLinesSink<W> {
+    fn publish(&self, message: Message) -> BoxFuture<'static, Result<(), capnp::Error>> {
+        let mut writer = self.writer.lock().unwrap();
+        let written = writeln!(writer, "{}", json_line(&message)).and_then(|_| writer.flush());
+        futures::future::ready(
+            written.map_err(|e| {
+                capnp::Error::failed(format!("Failed to write {}: {}", message.key, e))
+            }),
+        )
+        .boxed()
+    }
+}
+
+// Chosen by configuration, e.g. MESSAGE_SINK=file:/tmp/messages.jsonl
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum SinkConfig {
+    #[default]
+    Zenoh,
+    Stdout,
+    File(PathBuf),
+}
+
+impl FromStr for SinkConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zenoh" => Ok(SinkConfig::Zenoh),
+            "stdout" => Ok(SinkConfig::Stdout),
+            _ => match s.strip_prefix("file:") {
+                Some(path) if !path.is_empty() => Ok(SinkConfig::File(path.into())),
+                _ => Err(format!(
+                    "Unknown message sink {}, expected zenoh, stdout or file:<path>",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl SinkConfig {
+    // `zenoh` is the sink used when Zenoh is configured
+    pub fn open(&self, zenoh: ZenohSink) -> io::Result<Arc<dyn MessageSink>> {
+        Ok(match self {
+            SinkConfig::Zenoh => Arc::new(zenoh),
+            SinkConfig::Stdout => Arc::new(JsonLinesSink::new(io::stdout())),
+            SinkConfig::File(path) => Arc::new(JsonLinesSink::new(
+                File::options().create(true).append(true).open(path)?,
+            )),
+        })
+    }
+}
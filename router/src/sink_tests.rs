@@ -0,0 +1,236 @@
+// Services publishing through a sink, without a network or on an isolated session
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use zenoh::qos::{CongestionControl, Priority, Reliability};
+
+use crate::clock::Clock;
+use crate::schema_capnp::twist_service;
+use crate::sink::{
+    Attachment, Buffering, Encoding, JsonLinesSink, Message, MessageSink, Origin, Qos,
+    RecordingSink, SinkConfig, Source, ZenohSink,
+};
+use crate::{TwistOptions, TwistSinkService};
+
+#[tokio::test]
+async fn twist_service_publishes_cdr_to_sink() {
+    let recorder = Arc::new(RecordingSink::new());
+    let options = TwistOptions {
+        sink: recorder.clone(),
+        qos: "1:express".parse().unwrap(),
+        source: Arc::new(Source::new("teleop")),
+        clock: Clock::wall(),
+        stamped: Arc::new(HashSet::new()),
+        dead_reckoning: None,
+    };
+    let twist: twist_service::Client =
+        capnp_rpc::new_client(TwistSinkService::for_robot("turtle1", &options));
+
+    for _ in 0..2 {
+        let mut request = twist.do_twist_request();
+        let mut data = request.get().init_data();
+        data.reborrow().init_linear().set_x(2.0);
+        data.reborrow().init_angular().set_z(0.5);
+        request.send().promise.await.unwrap();
+    }
+
+    let messages = recorder.take();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].origin.client_id, "teleop");
+    assert_eq!(messages[0].origin.sequence, 0);
+    assert_eq!(messages[1].origin.sequence, 1);
+    assert!(messages[0].origin.received_at <= messages[1].origin.received_at);
+    assert_eq!(messages[0].key, "turtle1/cmd_vel");
+    assert_eq!(
+        messages[0].encoding,
+        Encoding::cdr("geometry_msgs/msg/Twist")
+    );
+    assert_eq!(messages[0].qos.priority, Priority::RealTime);
+    assert!(messages[0].qos.express);
+    // Encapsulation header, then linear x and angular z among the six doubles
+    assert_eq!(messages[0].payload.len(), 4 + 6 * 8);
+    assert_eq!(messages[0].payload[4..12], 2.0f64.to_le_bytes());
+    assert_eq!(messages[0].payload[44..52], 0.5f64.to_le_bytes());
+}
+
+#[tokio::test]
+async fn json_lines_embed_json_and_hex_encode_the_rest() {
+    let mut output = Vec::new();
+    {
+        let sink = JsonLinesSink::new(&mut output);
+        for (payload, encoding) in [
+            (br#"{"x": 1}"#.to_vec(), Encoding::Json),
+            (vec![0x00, 0x01, 0xff], Encoding::cdr("std_msgs/msg/Byte")),
+        ] {
+            sink.publish(Message {
+                key: "robot/topic".to_string(),
+                payload,
+                encoding,
+                qos: Qos::default(),
+                origin: Source::new("test").origin("robot/topic"),
+            })
+            .await
+            .unwrap();
+        }
+    }
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["key"], "robot/topic");
+    assert_eq!(lines[0]["payload"], serde_json::json!({"x": 1}));
+    assert_eq!(lines[1]["encoding"], "application/cdr;std_msgs/msg/Byte");
+    assert_eq!(lines[1]["payload"], "0001ff");
+}
+
+#[test]
+fn sink_config_parses() {
+    assert_eq!("zenoh".parse(), Ok(SinkConfig::Zenoh));
+    assert_eq!("stdout".parse(), Ok(SinkConfig::Stdout));
+    assert_eq!(
+        "file:/tmp/messages.jsonl".parse(),
+        Ok(SinkConfig::File("/tmp/messages.jsonl".into()))
+    );
+    assert!("file:".parse::<SinkConfig>().is_err());
+    assert!("kafka".parse::<SinkConfig>().is_err());
+}
+
+#[test]
+fn qos_parses_like_pub_priorities() {
+    assert_eq!(
+        "1:express:block:best_effort".parse(),
+        Ok(Qos {
+            priority: Priority::RealTime,
+            congestion_control: CongestionControl::Block,
+            express: true,
+            reliability: Reliability::BestEffort,
+        })
+    );
+    assert_eq!(
+        "5".parse(),
+        Ok(Qos {
+            priority: Priority::Data,
+            ..Qos::default()
+        })
+    );
+    assert!("0".parse::<Qos>().is_err());
+    assert!("2:fast".parse::<Qos>().is_err());
+}
+
+#[test]
+fn buffering_parses() {
+    assert_eq!("drop".parse(), Ok(Buffering::Drop));
+    assert_eq!("latest".parse(), Ok(Buffering::Latest));
+    assert_eq!(
+        "fifo:100:0.5".parse(),
+        Ok(Buffering::Fifo {
+            capacity: 100,
+            ttl: Duration::from_millis(500),
+        })
+    );
+    assert!("fifo:100".parse::<Buffering>().is_err());
+    assert!("fifo:100:-1".parse::<Buffering>().is_err());
+    assert!("forever".parse::<Buffering>().is_err());
+}
+
+#[test]
+fn sequences_count_per_key() {
+    let source = Source::new("client");
+    let sequences: Vec<u64> = ["a", "a", "b", "a"]
+        .iter()
+        .map(|key| source.origin(key).sequence)
+        .collect();
+    assert_eq!(sequences, [0, 1, 0, 2]);
+}
+
+#[test]
+fn attachments_round_trip_as_json() {
+    let attachment = Attachment {
+        origin: Origin {
+            client_id: "client".to_string(),
+            sequence: 7,
+            received_at: 1_700_000_000_000_000_000,
+        },
+        timestamp: "7386690599959157260/33a34a3b2e8b4a1b".to_string(),
+    };
+    let encoded = attachment.encode();
+    let json: serde_json::Value = serde_json::from_slice(&encoded).unwrap();
+    assert_eq!(json["clientId"], "client");
+    assert_eq!(json["sequence"], 7);
+    assert_eq!(Attachment::decode(&encoded).unwrap(), attachment);
+}
+
+fn message(key: &str, qos: Qos) -> Message {
+    Message {
+        key: key.to_string(),
+        payload: vec![0x00, 0x01, 0x00, 0x00],
+        encoding: Encoding::cdr("std_msgs/msg/Empty"),
+        qos,
+        origin: Source::new("test").origin(key),
+    }
+}
+
+async fn isolated_session() -> zenoh::Session {
+    let mut config = zenoh::Config::default();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    zenoh::open(config).await.unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn a_stalled_key_does_not_hold_up_others() {
+    let session = isolated_session().await;
+    let sink = ZenohSink::new(session.clone());
+
+    // Stands in for a bulk put blocked on congestion or a long replay of its buffer
+    let bulk = sink.key_state("fleet/hello");
+    let stalled = bulk.lock().await;
+    let hello = tokio::spawn(sink.publish(message("fleet/hello", Qos::default())));
+
+    let teleop = Qos {
+        priority: Priority::RealTime,
+        express: true,
+        ..Qos::default()
+    };
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        sink.publish(message("turtle1/cmd_vel", teleop)),
+    )
+    .await
+    .expect("Twist waited for another key")
+    .unwrap();
+    assert!(!hello.is_finished());
+
+    drop(stalled);
+    hello.await.unwrap().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn a_replaying_key_does_not_hold_up_other_buffered_keys() {
+    let session = isolated_session().await;
+    let sink = ZenohSink::new(session.clone()).with_buffering(
+        "fleet/**".try_into().unwrap(),
+        "fifo:10:60".parse().unwrap(),
+    );
+
+    // Held like the matching watcher holds it while putting a long buffer
+    let replaying = sink.key_state("fleet/robot1/hello");
+    let replaying = replaying.lock().await;
+
+    // Buffered keys wait for their matching status, without a lock shared with other keys
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        sink.publish(message("fleet/robot2/hello", Qos::default())),
+    )
+    .await
+    .expect("Put waited for a replay on another key")
+    .unwrap();
+    drop(replaying);
+}
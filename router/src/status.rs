@@ -0,0 +1,215 @@
+// Whether the router's session is linked to other Zenoh nodes and whether anything subscribes
+// to the topics it publishes, so operators know before driving blind
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use capnp::capability::Promise;
+use tokio::sync::watch;
+use zenoh::matching::MatchingListener;
+use zenoh::pubsub::{Publisher, Subscriber};
+use zenoh::sample::{Sample, SampleKind};
+
+use crate::schema_capnp::{self, connectivity_status, status_listener, subscription};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LinkState {
+    Connected,
+    NoSubscribers,
+    Disconnected,
+}
+
+impl From<LinkState> for schema_capnp::LinkState {
+    fn from(state: LinkState) -> Self {
+        match state {
+            LinkState::Connected => schema_capnp::LinkState::Connected,
+            LinkState::NoSubscribers => schema_capnp::LinkState::NoSubscribers,
+            LinkState::Disconnected => schema_capnp::LinkState::Disconnected,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Status {
+    // Zenoh ids of the nodes the session has transports to
+    pub peers: BTreeSet<String>,
+    // Configured publishers and whether subscribers match them
+    pub topics: BTreeMap<String, bool>,
+}
+
+impl Status {
+    // Matching subscribers may be in-process, so a topic can be connected without transports
+    pub fn topic_state(&self, topic: &str) -> LinkState {
+        match self.topics.get(topic) {
+            Some(true) => LinkState::Connected,
+            _ if self.peers.is_empty() => LinkState::Disconnected,
+            _ => LinkState::NoSubscribers,
+        }
+    }
+
+    pub fn state(&self) -> LinkState {
+        if self.peers.is_empty() {
+            return LinkState::Disconnected;
+        }
+        self.topics
+            .keys()
+            .map(|topic| self.topic_state(topic))
+            .max()
+            .unwrap_or(LinkState::Connected)
+    }
+
+    pub fn write(&self, mut builder: connectivity_status::Builder) {
+        builder.set_state(self.state().into());
+        let mut peers = builder.reborrow().init_peers(self.peers.len() as u32);
+        for (i, peer) in self.peers.iter().enumerate() {
+            peers.set(i as u32, peer);
+        }
+        let mut topics = builder.init_topics(self.topics.len() as u32);
+        for (i, topic) in self.topics.keys().enumerate() {
+            let mut entry = topics.reborrow().get(i as u32);
+            entry.set_topic(topic);
+            entry.set_state(self.topic_state(topic).into());
+        }
+    }
+}
+
+// The last chunk of a transport's admin key
+fn peer_zid(sample: &Sample) -> String {
+    let key = sample.key_expr().as_str();
+    key.rsplit('/').next().unwrap_or(key).to_string()
+}
+
+fn failed(e: zenoh::Error) -> capnp::Error {
+    capnp::Error::failed(format!("Failed to monitor zenoh status: {}", e))
+}
+
+// Follows transport events from the session's admin space and the matching status of a
+// publisher per configured topic
+pub struct StatusMonitor {
+    status: watch::Receiver<Status>,
+    _transports: Subscriber<()>,
+    _publishers: Vec<(Publisher<'static>, MatchingListener<()>)>,
+}
+
+impl StatusMonitor {
+    pub async fn start(
+        session: &zenoh::Session,
+        topics: impl IntoIterator<Item = String>,
+    ) -> Result<Self, capnp::Error> {
+        let (tx, status) = watch::channel(Status::default());
+        let tx = Arc::new(tx);
+
+        // Put when a transport opens and deleted when it closes, links are a level below
+        let transports_key = format!("@/{}/session/transport/unicast/*", session.zid());
+        let updates = tx.clone();
+        let transports = session
+            .declare_subscriber(&transports_key)
+            .callback(move |sample| {
+                let peer = peer_zid(&sample);
+                updates.send_if_modified(|status| match sample.kind() {
+                    SampleKind::Put => status.peers.insert(peer),
+                    SampleKind::Delete => status.peers.remove(&peer),
+                });
+            })
+            .await
+            .map_err(failed)?;
+        // Transports opened before the subscriber, as the admin space lists them. Zenoh answers
+        // it in place and blocks, which the LocalSet serving RPC does not allow
+        let admin = session.clone();
+        let linked = tokio::spawn(async move {
+            let replies = admin.get(&transports_key).await?;
+            let mut linked = Vec::new();
+            while let Ok(reply) = replies.recv_async().await {
+                if let Ok(sample) = reply.result() {
+                    linked.push(peer_zid(sample));
+                }
+            }
+            Ok::<_, zenoh::Error>(linked)
+        })
+        .await
+        .map_err(|e| capnp::Error::failed(format!("Failed to list zenoh transports: {}", e)))?
+        .map_err(failed)?;
+        tx.send_modify(|status| status.peers.extend(linked));
+
+        let mut publishers = Vec::new();
+        for topic in topics {
+            let publisher = session
+                .declare_publisher(topic.clone())
+                .await
+                .map_err(failed)?;
+            let updates = tx.clone();
+            let key = topic.clone();
+            let listener = publisher
+                .matching_listener()
+                .callback(move |matching| {
+                    updates.send_if_modified(|status| {
+                        status.topics.insert(key.clone(), matching.matching())
+                            != Some(matching.matching())
+                    });
+                })
+                .await
+                .map_err(failed)?;
+            let matching = publisher
+                .matching_status()
+                .await
+                .map_err(failed)?
+                .matching();
+            tx.send_modify(|status| {
+                status.topics.entry(topic).or_insert(matching);
+            });
+            publishers.push((publisher, listener));
+        }
+
+        Ok(Self {
+            status,
+            _transports: transports,
+            _publishers: publishers,
+        })
+    }
+
+    pub fn watch(&self) -> watch::Receiver<Status> {
+        self.status.clone()
+    }
+}
+
+// Pushes status changes to a client, stops when canceled or dropped
+pub struct StatusSubscription {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl StatusSubscription {
+    pub fn start(mut status: watch::Receiver<Status>, listener: status_listener::Client) -> Self {
+        let task = tokio::task::spawn_local(async move {
+            loop {
+                let current = status.borrow_and_update().clone();
+                let mut request = listener.on_status_request();
+                current.write(request.get().init_status());
+                if let Err(e) = request.send().promise.await {
+                    eprintln!("Failed to deliver status to client: {}", e);
+                    break;
+                }
+                if status.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+        Self { task }
+    }
+}
+
+impl Drop for StatusSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl subscription::Server for StatusSubscription {
+    fn cancel(
+        &mut self,
+        _: subscription::CancelParams,
+        _: subscription::CancelResults,
+    ) -> Promise<(), capnp::Error> {
+        self.task.abort();
+        Promise::ok(())
+    }
+}
@@ -0,0 +1,45 @@
+// Connectivity reported per topic and overall
+
+use crate::status::{LinkState, Status};
+
+fn status(peers: &[&str], topics: &[(&str, bool)]) -> Status {
+    Status {
+        peers: peers.iter().map(|peer| peer.to_string()).collect(),
+        topics: topics
+            .iter()
+            .map(|(topic, matching)| (topic.to_string(), *matching))
+            .collect(),
+    }
+}
+
+#[test]
+fn the_worst_topic_decides_the_state() {
+    let linked = status(
+        &["bridge"],
+        &[("fleet/hello", true), ("turtle1/cmd_vel", false)],
+    );
+    assert_eq!(linked.topic_state("fleet/hello"), LinkState::Connected);
+    assert_eq!(
+        linked.topic_state("turtle1/cmd_vel"),
+        LinkState::NoSubscribers
+    );
+    assert_eq!(linked.state(), LinkState::NoSubscribers);
+
+    assert_eq!(
+        status(&["bridge"], &[("turtle1/cmd_vel", true)]).state(),
+        LinkState::Connected
+    );
+    assert_eq!(status(&["bridge"], &[]).state(), LinkState::Connected);
+}
+
+#[test]
+fn no_transports_is_disconnected() {
+    // In-process subscribers still match
+    let unlinked = status(&[], &[("fleet/hello", true), ("turtle1/cmd_vel", false)]);
+    assert_eq!(unlinked.topic_state("fleet/hello"), LinkState::Connected);
+    assert_eq!(
+        unlinked.topic_state("turtle1/cmd_vel"),
+        LinkState::Disconnected
+    );
+    assert_eq!(unlinked.state(), LinkState::Disconnected);
+}
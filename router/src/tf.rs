@@ -0,0 +1,391 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc;
+
+use crate::clock::Clock;
+use crate::ros_types::{builtin_interfaces, geometry_msgs, std_msgs, tf2_msgs, turtlesim};
+use crate::sink::{Encoding, RouterPublisher};
+
+// How long dynamic transforms are kept, matches the tf2 default
+const CACHE_NANOS: i64 = 10_000_000_000;
+
+#[derive(Debug)]
+pub struct TfError(pub String);
+
+impl fmt::Display for TfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TfError {}
+
+pub fn to_nanos(time: &builtin_interfaces::Time) -> i64 {
+    time.sec as i64 * 1_000_000_000 + time.nanosec as i64
+}
+
+pub fn from_nanos(nanos: i64) -> builtin_interfaces::Time {
+    builtin_interfaces::Time {
+        sec: nanos.div_euclid(1_000_000_000) as i32,
+        nanosec: nanos.rem_euclid(1_000_000_000) as u32,
+    }
+}
+
+// A rigid transform, rotation is a unit quaternion in x, y, z, w order
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Isometry {
+    pub translation: [f64; 3],
+    pub rotation: [f64; 4],
+}
+
+impl Isometry {
+    pub const IDENTITY: Self = Self {
+        translation: [0.0; 3],
+        rotation: [0.0, 0.0, 0.0, 1.0],
+    };
+
+    fn rotate(&self, v: [f64; 3]) -> [f64; 3] {
+        let [x, y, z, w] = self.rotation;
+        // v + 2w(q x v) + 2q x (q x v)
+        let t = [
+            2.0 * (y * v[2] - z * v[1]),
+            2.0 * (z * v[0] - x * v[2]),
+            2.0 * (x * v[1] - y * v[0]),
+        ];
+        [
+            v[0] + w * t[0] + (y * t[2] - z * t[1]),
+            v[1] + w * t[1] + (z * t[0] - x * t[2]),
+            v[2] + w * t[2] + (x * t[1] - y * t[0]),
+        ]
+    }
+
+    // The transform applying `other` first, then `self`
+    pub fn compose(&self, other: &Self) -> Self {
+        let [ax, ay, az, aw] = self.rotation;
+        let [bx, by, bz, bw] = other.rotation;
+        let moved = self.rotate(other.translation);
+        Self {
+            translation: [
+                self.translation[0] + moved[0],
+                self.translation[1] + moved[1],
+                self.translation[2] + moved[2],
+            ],
+            rotation: [
+                aw * bx + ax * bw + ay * bz - az * by,
+                aw * by - ax * bz + ay * bw + az * bx,
+                aw * bz + ax * by - ay * bx + az * bw,
+                aw * bw - ax * bx - ay * by - az * bz,
+            ],
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let [x, y, z, w] = self.rotation;
+        let inverse = Self {
+            translation: [0.0; 3],
+            rotation: [-x, -y, -z, w],
+        };
+        let t = inverse.rotate(self.translation);
+        Self {
+            translation: [-t[0], -t[1], -t[2]],
+            rotation: inverse.rotation,
+        }
+    }
+
+    // Linear interpolation of the translation and slerp of the rotation
+    pub fn interpolate(&self, other: &Self, ratio: f64) -> Self {
+        let mut dot: f64 = (0..4).map(|i| self.rotation[i] * other.rotation[i]).sum();
+        let mut end = other.rotation;
+        // Take the short way around
+        if dot < 0.0 {
+            dot = -dot;
+            end = end.map(|c| -c);
+        }
+        let (a, b) = if dot > 0.9995 {
+            (1.0 - ratio, ratio)
+        } else {
+            let theta = dot.acos();
+            let sin = theta.sin();
+            (
+                ((1.0 - ratio) * theta).sin() / sin,
+                (ratio * theta).sin() / sin,
+            )
+        };
+        let rotation: [f64; 4] = std::array::from_fn(|i| a * self.rotation[i] + b * end[i]);
+        let norm = rotation.iter().map(|c| c * c).sum::<f64>().sqrt();
+        Self {
+            translation: std::array::from_fn(|i| {
+                self.translation[i] + (other.translation[i] - self.translation[i]) * ratio
+            }),
+            rotation: rotation.map(|c| c / norm),
+        }
+    }
+}
+
+impl From<&geometry_msgs::Transform> for Isometry {
+    fn from(transform: &geometry_msgs::Transform) -> Self {
+        let (t, r) = (&transform.translation, &transform.rotation);
+        Self {
+            translation: [t.x, t.y, t.z],
+            rotation: [r.x, r.y, r.z, r.w],
+        }
+    }
+}
+
+impl From<Isometry> for geometry_msgs::Transform {
+    fn from(isometry: Isometry) -> Self {
+        let ([x, y, z], [qx, qy, qz, qw]) = (isometry.translation, isometry.rotation);
+        Self {
+            translation: geometry_msgs::Vector3 { x, y, z },
+            rotation: geometry_msgs::Quaternion {
+                x: qx,
+                y: qy,
+                z: qz,
+                w: qw,
+            },
+        }
+    }
+}
+
+// The transform from a frame's parent to the frame, over time
+struct Link {
+    parent: String,
+    // Static links hold a single sample that is valid at any time
+    is_static: bool,
+    samples: VecDeque<(i64, Isometry)>,
+}
+
+impl Link {
+    fn latest(&self) -> Option<i64> {
+        match self.is_static {
+            true => None,
+            false => self.samples.back().map(|(time, _)| *time),
+        }
+    }
+
+    fn at(&self, child: &str, time: i64) -> Result<Isometry, TfError> {
+        if self.is_static {
+            return Ok(self.samples[0].1);
+        }
+        let after = self.samples.partition_point(|(t, _)| *t < time);
+        match (
+            after.checked_sub(1).map(|i| self.samples[i]),
+            self.samples.get(after),
+        ) {
+            (_, Some((t, isometry))) if *t == time => Ok(*isometry),
+            (Some((t0, a)), Some((t1, b))) => {
+                Ok(a.interpolate(b, (time - t0) as f64 / (t1 - t0) as f64))
+            }
+            _ => Err(TfError(format!(
+                "Lookup of {} -> {} would require extrapolation to {}, buffer holds {} to {}",
+                self.parent,
+                child,
+                time,
+                self.samples.front().map_or(0, |(t, _)| *t),
+                self.samples.back().map_or(0, |(t, _)| *t),
+            ))),
+        }
+    }
+}
+
+// Time-buffered transform tree fed from /tf and /tf_static
+#[derive(Default)]
+pub struct TfBuffer {
+    links: HashMap<String, Link>,
+}
+
+impl TfBuffer {
+    pub fn insert(&mut self, transform: &geometry_msgs::TransformStamped, is_static: bool) {
+        let parent = transform.header.frame_id.trim_start_matches('/');
+        let child = transform.child_frame_id.trim_start_matches('/');
+        let time = to_nanos(&transform.header.stamp);
+        let isometry = Isometry::from(&transform.transform);
+
+        let link = self.links.entry(child.to_string()).or_insert_with(|| Link {
+            parent: parent.to_string(),
+            is_static,
+            samples: VecDeque::new(),
+        });
+        // A frame that moves to another parent starts over, static links keep one sample
+        if link.parent != parent || link.is_static != is_static || is_static {
+            link.parent = parent.to_string();
+            link.is_static = is_static;
+            link.samples.clear();
+        }
+
+        let at = link.samples.partition_point(|(t, _)| *t < time);
+        match link.samples.get_mut(at) {
+            Some(sample) if sample.0 == time => sample.1 = isometry,
+            _ => link.samples.insert(at, (time, isometry)),
+        }
+        let newest = link.samples.back().map_or(time, |(t, _)| *t);
+        while link
+            .samples
+            .front()
+            .is_some_and(|(t, _)| newest - t > CACHE_NANOS)
+        {
+            link.samples.pop_front();
+        }
+    }
+
+    // Frames from `frame` up to the root of its tree
+    fn chain<'a>(&'a self, frame: &'a str) -> Result<Vec<&'a str>, TfError> {
+        let mut chain = vec![frame];
+        while let Some(link) = self.links.get(*chain.last().unwrap()) {
+            if chain.len() > self.links.len() {
+                return Err(TfError(format!(
+                    "Transform tree above {} has a loop",
+                    frame
+                )));
+            }
+            chain.push(&link.parent);
+        }
+        Ok(chain)
+    }
+
+    // Transform from the first frame of `chain` to the parent of its last frame
+    fn to_ancestor(&self, chain: &[&str], time: i64) -> Result<Isometry, TfError> {
+        chain.iter().try_fold(Isometry::IDENTITY, |acc, frame| {
+            Ok(self.links[*frame].at(frame, time)?.compose(&acc))
+        })
+    }
+
+    // Transform taking data in `source` to `target`, at `time` in nanoseconds. A time of 0
+    // picks the latest time every transform along the way is known for. Returns the time used.
+    pub fn lookup(
+        &self,
+        target: &str,
+        source: &str,
+        time: i64,
+    ) -> Result<(Isometry, i64), TfError> {
+        let (target, source) = (
+            target.trim_start_matches('/'),
+            source.trim_start_matches('/'),
+        );
+        let source_chain = self.chain(source)?;
+        let target_chain = self.chain(target)?;
+        let Some(common) = source_chain.iter().position(|f| target_chain.contains(f)) else {
+            return Err(TfError(format!(
+                "Frames {} and {} are not connected",
+                target, source
+            )));
+        };
+        let common_in_target = target_chain
+            .iter()
+            .position(|f| *f == source_chain[common])
+            .unwrap();
+        let (source_links, target_links) =
+            (&source_chain[..common], &target_chain[..common_in_target]);
+
+        let time = match time {
+            0 => source_links
+                .iter()
+                .chain(target_links)
+                .filter_map(|frame| self.links[*frame].latest())
+                .min()
+                .unwrap_or(0),
+            time => time,
+        };
+        let from_source = self.to_ancestor(source_links, time)?;
+        let from_target = self.to_ancestor(target_links, time)?;
+        Ok((from_target.inverse().compose(&from_source), time))
+    }
+}
+
+fn insert_message(buffer: &Mutex<TfBuffer>, payload: &[u8], is_static: bool) {
+    match cdr::deserialize::<tf2_msgs::TFMessage>(payload) {
+        Ok(message) => {
+            let mut buffer = buffer.lock().unwrap();
+            for transform in &message.transforms {
+                buffer.insert(transform, is_static);
+            }
+        }
+        Err(e) => eprintln!("Failed to decode TFMessage: {}", e),
+    }
+}
+
+// Keeps the transform buffer up to date with the tf topics
+pub struct TfListener {
+    pub buffer: Arc<Mutex<TfBuffer>>,
+    _subscribers: Vec<zenoh::pubsub::Subscriber<()>>,
+}
+
+impl TfListener {
+    pub async fn declare(session: &zenoh::Session) -> Result<Self, capnp::Error> {
+        let buffer = Arc::new(Mutex::new(TfBuffer::default()));
+        let mut subscribers = Vec::new();
+        for (topic, is_static) in [("tf", false), ("tf_static", true)] {
+            let sink = buffer.clone();
+            subscribers.push(
+                session
+                    .declare_subscriber(topic)
+                    .callback(move |sample| {
+                        insert_message(&sink, &sample.payload().to_bytes(), is_static)
+                    })
+                    .await
+                    .map_err(|e| {
+                        capnp::Error::failed(format!("Failed to subscribe {}: {}", topic, e))
+                    })?,
+            );
+        }
+        Ok(Self {
+            buffer,
+            _subscribers: subscribers,
+        })
+    }
+}
+
+// Broadcasts the pose of every turtlesim turtle on tf, as the transform from `parent_frame`
+// to a frame named after the turtle
+pub async fn broadcast_turtles(
+    session: &zenoh::Session,
+    parent_frame: String,
+    clock: Clock,
+    publisher: RouterPublisher,
+) -> Result<zenoh::pubsub::Subscriber<()>, capnp::Error> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let subscriber = session
+        .declare_subscriber("*/pose")
+        .callback(move |sample| {
+            let Some(turtle) = sample.key_expr().as_str().strip_suffix("/pose") else {
+                return;
+            };
+            match cdr::deserialize::<turtlesim::Pose>(&sample.payload().to_bytes()) {
+                Ok(pose) => {
+                    let _ = tx.send((turtle.to_string(), pose));
+                }
+                Err(e) => eprintln!("Failed to decode pose of {}: {}", turtle, e),
+            }
+        })
+        .await
+        .map_err(|e| capnp::Error::failed(format!("Failed to subscribe */pose: {}", e)))?;
+
+    tokio::spawn(async move {
+        while let Some((turtle, pose)) = rx.recv().await {
+            let half_yaw = pose.theta as f64 / 2.0;
+            let message = tf2_msgs::TFMessage {
+                transforms: vec![geometry_msgs::TransformStamped {
+                    header: std_msgs::Header {
+                        stamp: clock.stamp(),
+                        frame_id: parent_frame.clone(),
+                    },
+                    child_frame_id: turtle,
+                    transform: Isometry {
+                        translation: [pose.x as f64, pose.y as f64, 0.0],
+                        rotation: [0.0, 0.0, half_yaw.sin(), half_yaw.cos()],
+                    }
+                    .into(),
+                }],
+            };
+            let encoded = cdr::serialize::<_, _, cdr::CdrLe>(&message, cdr::Infinite).unwrap();
+            let published =
+                publisher.publish("tf", encoded, Encoding::cdr("tf2_msgs/msg/TFMessage"));
+            if let Err(e) = published.await {
+                eprintln!("Failed to broadcast turtle transform: {}", e);
+            }
+        }
+    });
+    Ok(subscriber)
+}
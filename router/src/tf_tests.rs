@@ -0,0 +1,108 @@
+// Transform buffer lookups through static and interpolated dynamic links
+
+use std::f64::consts::FRAC_PI_2;
+
+use crate::ros_types::{geometry_msgs, std_msgs};
+use crate::tf::{Isometry, TfBuffer, from_nanos};
+
+const SECOND: i64 = 1_000_000_000;
+
+fn transform(
+    parent: &str,
+    child: &str,
+    time: i64,
+    xyz: [f64; 3],
+    yaw: f64,
+) -> geometry_msgs::TransformStamped {
+    geometry_msgs::TransformStamped {
+        header: std_msgs::Header {
+            stamp: from_nanos(time),
+            frame_id: parent.to_string(),
+        },
+        child_frame_id: child.to_string(),
+        transform: Isometry {
+            translation: xyz,
+            rotation: [0.0, 0.0, (yaw / 2.0).sin(), (yaw / 2.0).cos()],
+        }
+        .into(),
+    }
+}
+
+fn assert_close(actual: &Isometry, translation: [f64; 3], yaw: f64) {
+    let expected = [0.0, 0.0, (yaw / 2.0).sin(), (yaw / 2.0).cos()];
+    for (a, e) in actual.translation.iter().zip(translation) {
+        assert!(
+            (a - e).abs() < 1e-9,
+            "{:?} != {:?}",
+            actual.translation,
+            translation
+        );
+    }
+    for (a, e) in actual.rotation.iter().zip(expected) {
+        assert!(
+            (a - e).abs() < 1e-9,
+            "{:?} != {:?}",
+            actual.rotation,
+            expected
+        );
+    }
+}
+
+// world -> odom is static, odom -> base_link moves and turns between 1s and 3s
+fn buffer() -> TfBuffer {
+    let mut buffer = TfBuffer::default();
+    buffer.insert(&transform("world", "odom", 0, [1.0, 0.0, 0.0], 0.0), true);
+    buffer.insert(
+        &transform("odom", "base_link", SECOND, [0.0; 3], 0.0),
+        false,
+    );
+    buffer.insert(
+        &transform("odom", "base_link", 3 * SECOND, [2.0, 0.0, 0.0], FRAC_PI_2),
+        false,
+    );
+    buffer.insert(
+        &transform("/base_link", "laser", 0, [0.5, 0.0, 0.0], 0.0),
+        true,
+    );
+    buffer
+}
+
+#[test]
+fn lookups_interpolate_between_samples() {
+    let buffer = buffer();
+    let (found, time) = buffer.lookup("world", "base_link", 2 * SECOND).unwrap();
+    assert_eq!(time, 2 * SECOND);
+    assert_close(&found, [2.0, 0.0, 0.0], FRAC_PI_2 / 2.0);
+}
+
+#[test]
+fn lookups_compose_the_inverse_path() {
+    let buffer = buffer();
+    let (forward, _) = buffer.lookup("world", "laser", 3 * SECOND).unwrap();
+    assert_close(&forward, [3.0, 0.5, 0.0], FRAC_PI_2);
+    let (backward, _) = buffer.lookup("laser", "world", 3 * SECOND).unwrap();
+    assert_close(&backward.compose(&forward), [0.0; 3], 0.0);
+}
+
+#[test]
+fn time_zero_uses_the_latest_common_time() {
+    let buffer = buffer();
+    let (found, time) = buffer.lookup("odom", "laser", 0).unwrap();
+    assert_eq!(time, 3 * SECOND);
+    assert_close(&found, [2.0, 0.5, 0.0], FRAC_PI_2);
+
+    // Static links alone are valid at any time
+    let (found, time) = buffer.lookup("laser", "base_link", 0).unwrap();
+    assert_eq!(time, 0);
+    assert_close(&found, [-0.5, 0.0, 0.0], 0.0);
+}
+
+#[test]
+fn lookups_do_not_extrapolate() {
+    let mut buffer = buffer();
+    assert!(buffer.lookup("world", "base_link", 4 * SECOND).is_err());
+    assert!(buffer.lookup("world", "base_link", SECOND / 2).is_err());
+
+    buffer.insert(&transform("map", "robot2", SECOND, [0.0; 3], 0.0), false);
+    assert!(buffer.lookup("world", "robot2", SECOND).is_err());
+}
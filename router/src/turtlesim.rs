@@ -0,0 +1,364 @@
+use std::sync::{Arc, Mutex};
+
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+use serde::{Deserialize, Serialize};
+
+use crate::action::ActiveGoal;
+use crate::query::{Empty, call_service};
+use crate::schema_capnp::{
+    goal_handle, pose_service, rotate_absolute_goal_handle, turtlesim_service,
+};
+use crate::{TwistOptions, TwistSinkService};
+
+// Request/response types of the turtlesim services, field order matches the .srv files
+#[derive(Deserialize, Serialize, PartialEq)]
+struct SpawnRequest {
+    x: f32,
+    y: f32,
+    theta: f32,
+    name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct SpawnResponse {
+    name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct KillRequest {
+    name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct SetPenRequest {
+    r: u8,
+    g: u8,
+    b: u8,
+    width: u8,
+    off: u8,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct TeleportAbsoluteRequest {
+    x: f32,
+    y: f32,
+    theta: f32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct TeleportRelativeRequest {
+    linear: f32,
+    angular: f32,
+}
+
+// turtlesim/action/RotateAbsolute
+#[derive(Deserialize, Serialize, PartialEq)]
+struct RotateAbsoluteGoal {
+    theta: f32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct RotateAbsoluteResult {
+    delta: f32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct RotateAbsoluteFeedback {
+    remaining: f32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+pub struct Pose {
+    pub x: f32,
+    pub y: f32,
+    pub theta: f32,
+    pub linear_velocity: f32,
+    pub angular_velocity: f32,
+}
+
+// Keeps the latest pose published by turtlesim for a single turtle
+pub struct PoseZenohService {
+    topic: String,
+    latest: Arc<Mutex<Option<Pose>>>,
+    _subscriber: zenoh::pubsub::Subscriber<()>,
+}
+
+impl PoseZenohService {
+    pub async fn declare(session: &zenoh::Session, turtle: &str) -> Result<Self, capnp::Error> {
+        let topic = format!("{}/pose", turtle);
+        let latest = Arc::new(Mutex::new(None));
+        let sink = latest.clone();
+        let subscriber = session
+            .declare_subscriber(&topic)
+            .callback(
+                move |sample| match cdr::deserialize::<Pose>(&sample.payload().to_bytes()) {
+                    Ok(pose) => *sink.lock().unwrap() = Some(pose),
+                    Err(e) => eprintln!("Failed to decode pose: {}", e),
+                },
+            )
+            .await
+            .map_err(|e| capnp::Error::failed(format!("Failed to subscribe {}: {}", topic, e)))?;
+
+        Ok(Self {
+            topic,
+            latest,
+            _subscriber: subscriber,
+        })
+    }
+}
+
+impl pose_service::Server for PoseZenohService {
+    fn get_pose(
+        &mut self,
+        _params: pose_service::GetPoseParams,
+        mut results: pose_service::GetPoseResults,
+    ) -> Promise<(), capnp::Error> {
+        let Some(pose) = self.latest.lock().unwrap().clone() else {
+            return Promise::err(capnp::Error::failed(format!(
+                "No pose received yet on {}",
+                self.topic
+            )));
+        };
+
+        let mut data = results.get().init_pose();
+        data.set_x(pose.x);
+        data.set_y(pose.y);
+        data.set_theta(pose.theta);
+        data.set_linear_velocity(pose.linear_velocity);
+        data.set_angular_velocity(pose.angular_velocity);
+        Promise::ok(())
+    }
+}
+
+// Goal handle of a turtle's rotate_absolute action, cancels the goal when dropped
+pub struct RotateAbsoluteGoalHandle {
+    goal: ActiveGoal,
+}
+
+impl goal_handle::Server for RotateAbsoluteGoalHandle {
+    fn get_status(
+        &mut self,
+        _: goal_handle::GetStatusParams,
+        mut results: goal_handle::GetStatusResults,
+    ) -> Promise<(), capnp::Error> {
+        results.get().set_status(self.goal.status().into());
+        Promise::ok(())
+    }
+
+    fn cancel(
+        &mut self,
+        _: goal_handle::CancelParams,
+        _: goal_handle::CancelResults,
+    ) -> Promise<(), capnp::Error> {
+        Promise::from_future(self.goal.cancel())
+    }
+}
+
+impl rotate_absolute_goal_handle::Server for RotateAbsoluteGoalHandle {
+    fn get_result(
+        &mut self,
+        _: rotate_absolute_goal_handle::GetResultParams,
+        mut results: rotate_absolute_goal_handle::GetResultResults,
+    ) -> Promise<(), capnp::Error> {
+        let result = self.goal.result::<RotateAbsoluteResult>();
+        Promise::from_future(async move {
+            let (status, result) = result.await?;
+            let mut results = results.get();
+            results.set_status(status.into());
+            results.set_delta(result.delta);
+            Ok(())
+        })
+    }
+}
+
+// Typed access to the services of the turtlesim node
+pub struct TurtlesimZenohService {
+    pub session: zenoh::Session,
+    // How twists to spawned turtles are sent
+    pub twist_options: TwistOptions,
+}
+
+// Turtle names come from clients, strip slashes so they map onto a key expression
+fn turtle_name(name: capnp::text::Reader) -> Result<String, capnp::Error> {
+    let name = name.to_str()?.trim_matches('/');
+    if name.is_empty() {
+        return Err(capnp::Error::failed("Turtle name is empty".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+impl turtlesim_service::Server for TurtlesimZenohService {
+    fn spawn(
+        &mut self,
+        params: turtlesim_service::SpawnParams,
+        mut results: turtlesim_service::SpawnResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let request = SpawnRequest {
+            x: params.get_x(),
+            y: params.get_y(),
+            theta: params.get_theta(),
+            // An empty name lets turtlesim pick one
+            name: pry!(pry!(params.get_name()).to_string()),
+        };
+
+        let session = self.session.clone();
+        let twist_options = self.twist_options.clone();
+        Promise::from_future(async move {
+            let response: SpawnResponse = call_service(&session, "spawn", &request).await?;
+            println!("Spawned turtle {}", response.name);
+
+            let pose = PoseZenohService::declare(&session, &response.name).await?;
+            let mut results = results.get();
+            results.set_name(&response.name);
+            results.set_twist(capnp_rpc::new_client(TwistSinkService::for_robot(
+                &response.name,
+                &twist_options,
+            )));
+            results.set_pose(capnp_rpc::new_client(pose));
+            Ok(())
+        })
+    }
+
+    fn kill(
+        &mut self,
+        params: turtlesim_service::KillParams,
+        _: turtlesim_service::KillResults,
+    ) -> Promise<(), capnp::Error> {
+        let name = pry!(turtle_name(pry!(pry!(params.get()).get_name())));
+        let session = self.session.clone();
+        Promise::from_future(async move {
+            let _: Empty = call_service(&session, "kill", &KillRequest { name }).await?;
+            Ok(())
+        })
+    }
+
+    fn set_pen(
+        &mut self,
+        params: turtlesim_service::SetPenParams,
+        _: turtlesim_service::SetPenResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let name = pry!(turtle_name(pry!(params.get_name())));
+        let request = SetPenRequest {
+            r: params.get_r(),
+            g: params.get_g(),
+            b: params.get_b(),
+            width: params.get_width(),
+            off: params.get_off() as u8,
+        };
+        let session = self.session.clone();
+        Promise::from_future(async move {
+            let _: Empty = call_service(&session, &format!("{}/set_pen", name), &request).await?;
+            Ok(())
+        })
+    }
+
+    fn teleport_absolute(
+        &mut self,
+        params: turtlesim_service::TeleportAbsoluteParams,
+        _: turtlesim_service::TeleportAbsoluteResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let name = pry!(turtle_name(pry!(params.get_name())));
+        let request = TeleportAbsoluteRequest {
+            x: params.get_x(),
+            y: params.get_y(),
+            theta: params.get_theta(),
+        };
+        let session = self.session.clone();
+        Promise::from_future(async move {
+            let key = format!("{}/teleport_absolute", name);
+            let _: Empty = call_service(&session, &key, &request).await?;
+            Ok(())
+        })
+    }
+
+    fn teleport_relative(
+        &mut self,
+        params: turtlesim_service::TeleportRelativeParams,
+        _: turtlesim_service::TeleportRelativeResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let name = pry!(turtle_name(pry!(params.get_name())));
+        let request = TeleportRelativeRequest {
+            linear: params.get_linear(),
+            angular: params.get_angular(),
+        };
+        let session = self.session.clone();
+        Promise::from_future(async move {
+            let key = format!("{}/teleport_relative", name);
+            let _: Empty = call_service(&session, &key, &request).await?;
+            Ok(())
+        })
+    }
+
+    fn clear(
+        &mut self,
+        _: turtlesim_service::ClearParams,
+        _: turtlesim_service::ClearResults,
+    ) -> Promise<(), capnp::Error> {
+        let session = self.session.clone();
+        Promise::from_future(async move {
+            let _: Empty = call_service(&session, "clear", &Empty::default()).await?;
+            Ok(())
+        })
+    }
+
+    fn get_turtle(
+        &mut self,
+        params: turtlesim_service::GetTurtleParams,
+        mut results: turtlesim_service::GetTurtleResults,
+    ) -> Promise<(), capnp::Error> {
+        let name = pry!(turtle_name(pry!(pry!(params.get()).get_name())));
+        let session = self.session.clone();
+        let twist_options = self.twist_options.clone();
+        Promise::from_future(async move {
+            let pose = PoseZenohService::declare(&session, &name).await?;
+            let mut results = results.get();
+            results.set_twist(capnp_rpc::new_client(TwistSinkService::for_robot(
+                &name,
+                &twist_options,
+            )));
+            results.set_pose(capnp_rpc::new_client(pose));
+            Ok(())
+        })
+    }
+
+    fn rotate_absolute(
+        &mut self,
+        params: turtlesim_service::RotateAbsoluteParams,
+        mut results: turtlesim_service::RotateAbsoluteResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let name = pry!(turtle_name(pry!(params.get_name())));
+        let goal = RotateAbsoluteGoal {
+            theta: params.get_theta(),
+        };
+        let callback = pry!(params.get_feedback());
+        let session = self.session.clone();
+        Promise::from_future(async move {
+            let action = format!("{}/rotate_absolute", name);
+            let (goal, mut feedback) =
+                ActiveGoal::send::<_, RotateAbsoluteFeedback>(&session, &action, &goal).await?;
+
+            // Forward feedback to the client callback until the goal is dropped
+            tokio::task::spawn_local(async move {
+                while let Some(message) = feedback.recv().await {
+                    let mut request = callback.on_feedback_request();
+                    request.get().set_remaining(message.remaining);
+                    if let Err(e) = request.send().promise.await {
+                        eprintln!("Failed to deliver feedback to client: {}", e);
+                        break;
+                    }
+                }
+            });
+
+            results
+                .get()
+                .set_goal(capnp_rpc::new_client(RotateAbsoluteGoalHandle { goal }));
+            Ok(())
+        })
+    }
+}
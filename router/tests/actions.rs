@@ -0,0 +1,210 @@
+// Goals of any action type in the registry, sent to an action server on a peer session
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+use cdr::{CdrLe, Infinite};
+use common::Harness;
+use router::TypeRegistry;
+use router::schema_capnp::{GoalStatus, goal_feedback, ros_value};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+const ACTION: &str = "turtle1/rotate_absolute";
+
+#[derive(Deserialize)]
+struct SendGoalRequest {
+    goal_id: [u8; 16],
+    theta: f32,
+}
+
+#[derive(Serialize)]
+struct SendGoalResponse {
+    accepted: bool,
+    sec: i32,
+    nanosec: u32,
+}
+
+#[derive(Serialize)]
+struct FeedbackMessage {
+    goal_id: [u8; 16],
+    remaining: f32,
+}
+
+#[derive(Serialize)]
+struct GetResultResponse {
+    status: i8,
+    delta: f32,
+}
+
+// Accepts every goal, reports half of the rotation as remaining and succeeds with the other
+// half as the delta
+async fn serve_rotate_absolute(session: &zenoh::Session) -> Vec<tokio::task::JoinHandle<()>> {
+    let send_goal = session
+        .declare_queryable(format!("{}/_action/send_goal", ACTION))
+        .await
+        .unwrap();
+    let get_result = session
+        .declare_queryable(format!("{}/_action/get_result", ACTION))
+        .await
+        .unwrap();
+    let feedback = session
+        .declare_publisher(format!("{}/_action/feedback", ACTION))
+        .await
+        .unwrap();
+    let (theta_tx, mut theta_rx) = mpsc::unbounded_channel();
+
+    let goals = tokio::spawn(async move {
+        while let Ok(query) = send_goal.recv_async().await {
+            let payload = query.payload().unwrap().to_bytes();
+            let request: SendGoalRequest = cdr::deserialize(&payload).unwrap();
+            let response = SendGoalResponse {
+                accepted: true,
+                sec: 0,
+                nanosec: 0,
+            };
+            query
+                .reply(
+                    query.key_expr().clone(),
+                    cdr::serialize::<_, _, CdrLe>(&response, Infinite).unwrap(),
+                )
+                .await
+                .unwrap();
+            let message = FeedbackMessage {
+                goal_id: request.goal_id,
+                remaining: request.theta / 2.0,
+            };
+            feedback
+                .put(cdr::serialize::<_, _, CdrLe>(&message, Infinite).unwrap())
+                .await
+                .unwrap();
+            let _ = theta_tx.send(request.theta);
+        }
+    });
+    let results = tokio::spawn(async move {
+        while let Ok(query) = get_result.recv_async().await {
+            let theta = theta_rx.recv().await.unwrap();
+            let response = GetResultResponse {
+                status: 4,
+                delta: theta / 2.0,
+            };
+            query
+                .reply(
+                    query.key_expr().clone(),
+                    cdr::serialize::<_, _, CdrLe>(&response, Infinite).unwrap(),
+                )
+                .await
+                .unwrap();
+        }
+    });
+    vec![goals, results]
+}
+
+struct Feedback(mpsc::UnboundedSender<f32>);
+
+impl goal_feedback::Server for Feedback {
+    fn on_feedback(
+        &mut self,
+        params: goal_feedback::OnFeedbackParams,
+        _: goal_feedback::OnFeedbackResults,
+    ) -> Promise<(), capnp::Error> {
+        let _ = self
+            .0
+            .send(pry!(float32_field(pry!(pry!(params.get()).get_feedback()))));
+        Promise::ok(())
+    }
+}
+
+// The single float32 field of a message value
+fn float32_field(value: ros_value::Reader) -> Result<f32, capnp::Error> {
+    let ros_value::Message(fields) = value.which()? else {
+        return Err(capnp::Error::failed("Not a message".to_string()));
+    };
+    match fields?.get(0).get_value()?.which()? {
+        ros_value::Float32(v) => Ok(v),
+        _ => Err(capnp::Error::failed("Not a float32".to_string())),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn send_goal_streams_feedback_and_resolves_with_the_result() {
+    let harness = Harness::new().await;
+    let _server = serve_rotate_absolute(&harness.peer).await;
+    let registry =
+        Arc::new(TypeRegistry::load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../msgs")).unwrap());
+
+    harness
+        .run(
+            |services| services.with_generic_actions(registry),
+            |bootstrap| async move {
+                let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+                let feedback: goal_feedback::Client = capnp_rpc::new_client(Feedback(feedback_tx));
+
+                // The server's queryables take a moment to reach the router
+                let mut goal = None;
+                for _ in 0..50 {
+                    let mut request = bootstrap.send_goal_request();
+                    let mut params = request.get();
+                    params.set_action(ACTION);
+                    params.set_action_type("turtlesim/RotateAbsolute");
+                    params.set_feedback(feedback.clone());
+                    let mut field = params.init_goal().init_message(1).get(0);
+                    field.set_name("theta");
+                    field.init_value().set_float32(1.5);
+                    if let Ok(response) = request.send().promise.await {
+                        goal = Some(response.get().unwrap().get_goal().unwrap());
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                let goal = goal.expect("Goal was never accepted");
+
+                let remaining = tokio::time::timeout(Duration::from_secs(5), feedback_rx.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(remaining, 0.75);
+
+                let response = goal.get_result_request().send().promise.await.unwrap();
+                let result = response.get().unwrap();
+                assert_eq!(result.get_status().unwrap(), GoalStatus::Succeeded);
+                assert_eq!(float32_field(result.get_result().unwrap()).unwrap(), 0.75);
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unknown_action_types_are_rejected() {
+    let harness = Harness::new().await;
+    let registry =
+        Arc::new(TypeRegistry::load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../msgs")).unwrap());
+
+    harness
+        .run(
+            |services| services.with_generic_actions(registry),
+            |bootstrap| async move {
+                let mut request = bootstrap.send_goal_request();
+                let mut params = request.get();
+                params.set_action(ACTION);
+                params.set_action_type("turtlesim/action/Teleport");
+                params.init_goal().init_message(0);
+                let error = match request.send().promise.await {
+                    Ok(_) => panic!("Goal of an unknown type was sent"),
+                    Err(e) => e,
+                };
+                assert!(
+                    error
+                        .to_string()
+                        .contains("Unknown action type turtlesim/action/Teleport"),
+                    "{}",
+                    error
+                );
+            },
+        )
+        .await;
+}
@@ -0,0 +1,133 @@
+// Messages put while nobody subscribes are replayed once someone does, unless they are motion
+// commands
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{Harness, expect_sample};
+use router::sink::{Attachment, Buffering, ZenohSink};
+use zenoh::key_expr::OwnedKeyExpr;
+
+fn zenoh_sink(harness: &Harness, key_expr: &str, buffering: Buffering) -> Arc<ZenohSink> {
+    let key_expr = OwnedKeyExpr::new(key_expr).unwrap();
+    Arc::new(ZenohSink::new(harness.session.clone()).with_buffering(key_expr, buffering))
+}
+
+fn sequence(sample: &zenoh::sample::Sample) -> u64 {
+    Attachment::decode(&sample.attachment().unwrap().to_bytes())
+        .unwrap()
+        .origin
+        .sequence
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hellos_are_replayed_to_late_subscribers() {
+    let harness = Harness::new().await;
+    let buffering = Buffering::Fifo {
+        capacity: 10,
+        ttl: Duration::from_secs(60),
+    };
+    let sink = zenoh_sink(&harness, "fleet/**", buffering);
+    let peer = harness.peer.clone();
+
+    harness
+        .run(
+            |services| services.with_sink(sink).with_hello_publisher("fleet/hello"),
+            |bootstrap| async move {
+                let hello = bootstrap
+                    .get_hello_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                for msg in ["first", "second"] {
+                    let mut request = hello.do_hello_request();
+                    request.get().init_data().set_msg(msg);
+                    request.send().promise.await.unwrap();
+                }
+                let latest = || {
+                    let mut request = bootstrap.get_latest_request();
+                    request.get().set_topic("fleet/hello");
+                    async move {
+                        let response = request.send().promise.await.unwrap();
+                        let samples = response.get().unwrap().get_samples().unwrap();
+                        samples.iter().map(|s| s.get_sequence()).collect::<Vec<_>>()
+                    }
+                };
+                // Nothing reached Zenoh yet
+                assert!(latest().await.is_empty());
+
+                let subscriber = peer.declare_subscriber("fleet/hello").await.unwrap();
+                let mut sequences = Vec::new();
+                for _ in 0..2 {
+                    let sample =
+                        tokio::time::timeout(Duration::from_secs(5), subscriber.recv_async())
+                            .await
+                            .expect("Buffered hello not replayed")
+                            .unwrap();
+                    sequences.push(sequence(&sample));
+                }
+                assert_eq!(sequences, [0, 1]);
+
+                // Cached once replayed, right after the put the peer just received
+                for _ in 0..50 {
+                    if latest().await == [1] {
+                        return;
+                    }
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+                panic!("Replayed hello not cached");
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn motion_commands_are_never_replayed() {
+    let harness = Harness::new().await;
+    let sink = zenoh_sink(&harness, "**", Buffering::Latest);
+    let peer = harness.peer.clone();
+
+    harness
+        .run(
+            |services| {
+                services
+                    .with_sink(sink)
+                    .with_twist_publisher("turtle1/cmd_vel")
+            },
+            |bootstrap| async move {
+                let twist = bootstrap
+                    .get_twist_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                let mut request = twist.do_twist_request();
+                request.get().init_data().init_linear().set_x(1.0);
+                request.send().promise.await.unwrap();
+
+                // The first command reaching the subscriber is one sent after it came
+                let subscriber = peer.declare_subscriber("turtle1/cmd_vel").await.unwrap();
+                let sample = expect_sample(&subscriber, || {
+                    let mut request = twist.do_twist_request();
+                    request.get().init_data().init_linear().set_x(2.0);
+                    async move {
+                        request.send().promise.await.unwrap();
+                    }
+                })
+                .await;
+                assert!(sequence(&sample) > 0);
+            },
+        )
+        .await;
+}
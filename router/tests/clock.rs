@@ -0,0 +1,64 @@
+// Provenance follows the router's clock, e.g. ROS sim time received on /clock
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::Harness;
+use router::clock::ClockSource;
+use router::ros_types::{builtin_interfaces, rosgraph_msgs};
+use router::sink::RecordingSink;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn calls_are_received_at_sim_time() {
+    let harness = Harness::new().await;
+    let recorder = Arc::new(RecordingSink::new());
+    let sink = recorder.clone();
+    let peer = harness.peer.clone();
+
+    harness
+        .run(
+            |services| {
+                services
+                    .with_sink(sink)
+                    .with_clock(ClockSource::Sim)
+                    .with_hello_publisher("fleet/hello")
+            },
+            |bootstrap| async move {
+                let hello = bootstrap
+                    .get_hello_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                let clock = rosgraph_msgs::Clock {
+                    clock: builtin_interfaces::Time {
+                        sec: 42,
+                        nanosec: 500,
+                    },
+                };
+                let encoded = cdr::serialize::<_, _, cdr::CdrLe>(&clock, cdr::Infinite).unwrap();
+
+                // The router's /clock subscriber takes a moment to reach the peer
+                for _ in 0..50 {
+                    peer.put("clock", encoded.clone()).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    let mut request = hello.do_hello_request();
+                    request.get().init_data().set_msg("hi");
+                    request.send().promise.await.unwrap();
+                    let messages = recorder.take();
+                    if messages[0].origin.received_at != 0 {
+                        assert_eq!(messages[0].origin.received_at, 42_000_000_500);
+                        return;
+                    }
+                }
+                panic!("No sim time received");
+            },
+        )
+        .await;
+}
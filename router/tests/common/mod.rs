@@ -0,0 +1,110 @@
+// Starts a router, a peer Zenoh session connected to it and an RPC client, all in-process
+
+// Each test binary uses its own subset of the helpers
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use capnp_rpc::{RpcSystem, rpc_twoparty_capnp, twoparty};
+use futures::AsyncReadExt;
+use router::schema_capnp::bootstrap;
+use router::{BootstrapServiceBuilder, Router, RouterConfig};
+use zenoh::handlers::FifoChannelHandler;
+use zenoh::pubsub::Subscriber;
+use zenoh::sample::Sample;
+
+pub struct Harness {
+    // Session of the router itself
+    pub session: zenoh::Session,
+    // Stands in for the robots, connected to the router's session over TCP
+    pub peer: zenoh::Session,
+}
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+async fn open_session(mode: &str, endpoints: (&str, &str)) -> zenoh::Session {
+    let mut config = zenoh::Config::default();
+    config
+        .insert_json5("mode", &format!("\"{}\"", mode))
+        .unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    config.insert_json5(endpoints.0, endpoints.1).unwrap();
+    zenoh::open(config).await.unwrap()
+}
+
+impl Harness {
+    // Zenoh blocks when opening a session or dropping its last handle, which is not allowed on
+    // a LocalSet, so the sessions are owned here and outlive the LocalSet of `run`
+    pub async fn new() -> Self {
+        let endpoint = format!("[\"tcp/127.0.0.1:{}\"]", free_port());
+        let session = open_session("router", ("listen/endpoints", &endpoint)).await;
+        let peer = open_session("peer", ("connect/endpoints", &endpoint)).await;
+        Self { session, peer }
+    }
+
+    // Serves the configured services on an ephemeral port and runs `test` with a client
+    // connected to it
+    pub async fn run<C, T, F>(&self, configure: C, test: T)
+    where
+        C: FnOnce(BootstrapServiceBuilder) -> BootstrapServiceBuilder,
+        T: FnOnce(bootstrap::Client) -> F,
+        F: Future<Output = ()>,
+    {
+        let services = configure(BootstrapServiceBuilder::new(self.session.clone()));
+        tokio::task::LocalSet::new()
+            .run_until(async move {
+                let router = Router::serve(RouterConfig::new(services).with_listen("127.0.0.1:0"))
+                    .await
+                    .unwrap();
+
+                let stream = tokio::net::TcpStream::connect(router.local_addr())
+                    .await
+                    .unwrap();
+                stream.set_nodelay(true).unwrap();
+                let (reader, writer) =
+                    tokio_util::compat::TokioAsyncReadCompatExt::compat(stream).split();
+                let network = Box::new(twoparty::VatNetwork::new(
+                    futures::io::BufReader::new(reader),
+                    futures::io::BufWriter::new(writer),
+                    rpc_twoparty_capnp::Side::Client,
+                    Default::default(),
+                ));
+                let mut rpc_system = RpcSystem::new(network, None);
+                let bootstrap = rpc_system.bootstrap(rpc_twoparty_capnp::Side::Server);
+                tokio::task::spawn_local(rpc_system);
+
+                test(bootstrap).await;
+                router.shutdown().await;
+            })
+            .await;
+    }
+}
+
+// Declarations take a moment to reach the other session, retries `action` until a sample
+// arrives on the subscriber
+pub async fn expect_sample<A, F>(
+    subscriber: &Subscriber<FifoChannelHandler<Sample>>,
+    mut action: A,
+) -> Sample
+where
+    A: FnMut() -> F,
+    F: Future<Output = ()>,
+{
+    for _ in 0..50 {
+        action().await;
+        if let Ok(sample) =
+            tokio::time::timeout(Duration::from_millis(100), subscriber.recv_async()).await
+        {
+            return sample.unwrap();
+        }
+    }
+    panic!("No sample on {}", subscriber.key_expr());
+}
@@ -0,0 +1,42 @@
+// getInfo lets clients discover what a router offers before asking for it
+
+mod common;
+
+use common::Harness;
+use router::clock::ClockSource;
+use router::schema_capnp;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_info_lists_configured_services_and_features() {
+    let harness = Harness::new().await;
+    harness
+        .run(
+            |services| {
+                services
+                    .with_twist_publisher("turtle1/cmd_vel")
+                    .with_turtlesim_service()
+                    .with_clock(ClockSource::Sim)
+            },
+            |bootstrap| async move {
+                let response = bootstrap.get_info_request().send().promise.await.unwrap();
+                let info = response.get().unwrap().get_info().unwrap();
+                let names = |list: capnp::text_list::Reader| -> Vec<String> {
+                    list.iter()
+                        .map(|name| name.unwrap().to_string().unwrap())
+                        .collect()
+                };
+
+                assert_eq!(
+                    info.get_router_version().unwrap().to_str().unwrap(),
+                    env!("CARGO_PKG_VERSION")
+                );
+                assert_eq!(info.get_schema_version(), schema_capnp::SCHEMA_VERSION);
+                assert_eq!(
+                    names(info.get_services().unwrap()),
+                    ["twist", "turtlesim", "latest"]
+                );
+                assert_eq!(names(info.get_features().unwrap()), ["simTime"]);
+            },
+        )
+        .await;
+}
@@ -0,0 +1,137 @@
+// The last samples the router put are answered to gets on their key and to getLatest
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{Harness, expect_sample};
+use router::sink::{Attachment, RecordingSink, ZenohSink};
+use zenoh::query::ConsolidationMode;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn last_twists_are_answered_to_gets_and_get_latest() {
+    let harness = Harness::new().await;
+    let subscriber = harness
+        .peer
+        .declare_subscriber("turtle1/cmd_vel")
+        .await
+        .unwrap();
+    let sink = Arc::new(ZenohSink::new(harness.session.clone()).with_history(2));
+    let peer = harness.peer.clone();
+
+    harness
+        .run(
+            |services| {
+                services
+                    .with_sink(sink)
+                    .with_twist_publisher("turtle1/cmd_vel")
+            },
+            |bootstrap| async move {
+                let twist = bootstrap
+                    .get_twist_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                for x in [1.0, 2.0, 3.0] {
+                    expect_sample(&subscriber, || {
+                        let mut request = twist.do_twist_request();
+                        request.get().init_data().init_linear().set_x(x);
+                        async move {
+                            request.send().promise.await.unwrap();
+                        }
+                    })
+                    .await;
+                }
+
+                let get = |key_expr: &'static str, consolidation| {
+                    let peer = peer.clone();
+                    async move {
+                        let replies = peer
+                            .get(key_expr)
+                            .consolidation(consolidation)
+                            .await
+                            .unwrap();
+                        let mut samples = Vec::new();
+                        while let Ok(reply) = replies.recv_async().await {
+                            samples.push(reply.into_result().unwrap());
+                        }
+                        samples
+                    }
+                };
+                // z_get consolidates to the last command, without consolidation the last two
+                // kept are returned. Retries until the subscriber was matched may have put
+                // more than three
+                let latest = get("turtle1/cmd_vel", ConsolidationMode::Auto).await;
+                assert_eq!(latest.len(), 1);
+                let samples = get("turtle1/cmd_vel", ConsolidationMode::None).await;
+                assert_eq!(samples.len(), 2);
+                let last = &samples[1];
+                assert_eq!(latest[0].payload(), last.payload());
+
+                // Wildcards reach the queryable too, replies are on the concrete key
+                let matched = get("turtle1/**", ConsolidationMode::Auto).await;
+                assert_eq!(matched.len(), 1);
+                assert_eq!(matched[0].key_expr().as_str(), "turtle1/cmd_vel");
+                assert_eq!(matched[0].payload(), last.payload());
+                assert_eq!(last.payload().to_bytes()[4..12], 3.0f64.to_le_bytes());
+                assert_eq!(
+                    last.encoding().to_string(),
+                    "application/cdr;geometry_msgs/msg/Twist"
+                );
+                let attachment =
+                    Attachment::decode(&last.attachment().unwrap().to_bytes()).unwrap();
+                assert_eq!(attachment.timestamp, last.timestamp().unwrap().to_string());
+
+                let mut request = bootstrap.get_latest_request();
+                request.get().set_topic("turtle1/cmd_vel");
+                let response = request.send().promise.await.unwrap();
+                let latest = response.get().unwrap().get_samples().unwrap();
+                let sequences: Vec<u64> = latest.iter().map(|s| s.get_sequence()).collect();
+                assert_eq!(
+                    sequences,
+                    [attachment.origin.sequence - 1, attachment.origin.sequence]
+                );
+                let newest = latest.get(1);
+                assert_eq!(
+                    newest.get_payload().unwrap(),
+                    last.payload().to_bytes().as_ref()
+                );
+                assert_eq!(
+                    newest.get_client_id().unwrap().to_str().unwrap(),
+                    attachment.origin.client_id
+                );
+                assert_eq!(
+                    newest.get_timestamp().unwrap().to_str().unwrap(),
+                    attachment.timestamp
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_latest_needs_a_sink_keeping_history() {
+    let harness = Harness::new().await;
+    harness
+        .run(
+            |services| services.with_sink(Arc::new(RecordingSink::new())),
+            |bootstrap| async move {
+                let mut request = bootstrap.get_latest_request();
+                request.get().set_topic("turtle1/cmd_vel");
+                let error = request.send().promise.await.err().unwrap();
+                assert!(
+                    error
+                        .to_string()
+                        .contains("Last value cache not configured"),
+                    "{}",
+                    error
+                );
+            },
+        )
+        .await;
+}
@@ -0,0 +1,228 @@
+// Typed messages published through getMessagePublisher and subscribed to through
+// Bootstrap.subscribe, against a peer session
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+use cdr::{CdrLe, Infinite};
+use common::{Harness, expect_sample};
+use router::TypeRegistry;
+use router::ros_types::{builtin_interfaces, geometry_msgs, nav_msgs, std_msgs};
+use router::schema_capnp::{
+    MessageType, bootstrap, message_listener, message_publisher, ros_message, subscription,
+};
+use tokio::sync::mpsc;
+
+fn registry() -> Arc<TypeRegistry> {
+    Arc::new(TypeRegistry::load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../msgs")).unwrap())
+}
+
+fn header(sec: i32, frame_id: &str) -> std_msgs::Header {
+    std_msgs::Header {
+        stamp: builtin_interfaces::Time { sec, nanosec: 0 },
+        frame_id: frame_id.to_string(),
+    }
+}
+
+fn odometry(sec: i32) -> nav_msgs::Odometry {
+    let mut odometry = nav_msgs::Odometry {
+        header: header(sec, "odom"),
+        child_frame_id: "base_link".to_string(),
+        ..Default::default()
+    };
+    odometry.pose.pose.position.x = 1.5;
+    odometry.pose.covariance[0] = 0.01;
+    odometry.twist.twist.linear.x = 0.5;
+    odometry
+}
+
+async fn get_message_publisher(
+    bootstrap: &bootstrap::Client,
+    message_type: MessageType,
+) -> message_publisher::Client {
+    let mut request = bootstrap.get_message_publisher_request();
+    request.get().set_topic("robot1/goal_pose");
+    request.get().set_message_type(message_type);
+    let response = request.send().promise.await.unwrap();
+    response.get().unwrap().get_publisher().unwrap()
+}
+
+struct Listener(mpsc::UnboundedSender<nav_msgs::Odometry>);
+
+impl message_listener::Server for Listener {
+    fn on_message(
+        &mut self,
+        params: message_listener::OnMessageParams,
+        _: message_listener::OnMessageResults,
+    ) -> Promise<(), capnp::Error> {
+        let message = pry!(pry!(params.get()).get_message());
+        let ros_message::Odometry(odometry) = pry!(message.which()) else {
+            return Promise::err(capnp::Error::failed("Not an Odometry".to_string()));
+        };
+        let _ = self.0.send(pry!(nav_msgs::Odometry::read(pry!(odometry))));
+        Promise::ok(())
+    }
+}
+
+async fn subscribe_odometry(
+    bootstrap: &bootstrap::Client,
+    max_rate: f32,
+) -> (
+    subscription::Client,
+    mpsc::UnboundedReceiver<nav_msgs::Odometry>,
+) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut request = bootstrap.subscribe_request();
+    let mut params = request.get();
+    params.set_topic("robot1/odom");
+    params.set_message_type(MessageType::Odometry);
+    params.set_listener(capnp_rpc::new_client(Listener(tx)));
+    params.init_options().set_max_rate(max_rate);
+    let response = request.send().promise.await.unwrap();
+    (response.get().unwrap().get_subscription().unwrap(), rx)
+}
+
+async fn put(publisher: &zenoh::pubsub::Publisher<'_>, odometry: &nav_msgs::Odometry) {
+    publisher
+        .put(cdr::serialize::<_, _, CdrLe>(odometry, Infinite).unwrap())
+        .await
+        .unwrap();
+}
+
+// The subscription takes a moment to reach the peer, puts until the first delivery
+async fn first_delivery(
+    publisher: &zenoh::pubsub::Publisher<'_>,
+    rx: &mut mpsc::UnboundedReceiver<nav_msgs::Odometry>,
+    odometry: &nav_msgs::Odometry,
+) -> nav_msgs::Odometry {
+    for _ in 0..50 {
+        put(publisher, odometry).await;
+        if let Ok(received) = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await {
+            return received.unwrap();
+        }
+    }
+    panic!("No Odometry delivered");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pose_stamped_is_published_as_cdr() {
+    let harness = Harness::new().await;
+    let subscriber = harness
+        .peer
+        .declare_subscriber("robot1/goal_pose")
+        .await
+        .unwrap();
+
+    harness
+        .run(
+            |services| services.with_message_services(registry()),
+            |bootstrap| async move {
+                let publisher = get_message_publisher(&bootstrap, MessageType::PoseStamped).await;
+                let mut pose = geometry_msgs::PoseStamped {
+                    header: header(7, "map"),
+                    ..Default::default()
+                };
+                pose.pose.position.x = 2.0;
+                pose.pose.orientation.w = 1.0;
+
+                let sample = expect_sample(&subscriber, || {
+                    let mut request = publisher.publish_request();
+                    pose.write(request.get().init_message().init_pose_stamped());
+                    async move {
+                        request.send().promise.await.unwrap();
+                    }
+                })
+                .await;
+                assert_eq!(sample.key_expr().as_str(), "robot1/goal_pose");
+                assert_eq!(
+                    sample.encoding().to_string(),
+                    "application/cdr;geometry_msgs/msg/PoseStamped"
+                );
+                let received: geometry_msgs::PoseStamped =
+                    cdr::deserialize(&sample.payload().to_bytes()).unwrap();
+                assert_eq!(received, pose);
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn messages_of_another_type_are_rejected() {
+    let harness = Harness::new().await;
+
+    harness
+        .run(
+            |services| services.with_message_services(registry()),
+            |bootstrap| async move {
+                let publisher = get_message_publisher(&bootstrap, MessageType::PoseStamped).await;
+                let mut request = publisher.publish_request();
+                geometry_msgs::Twist::default().write(request.get().init_message().init_twist());
+                let error = match request.send().promise.await {
+                    Ok(_) => panic!("Twist was published as a PoseStamped"),
+                    Err(e) => e,
+                };
+                assert!(
+                    error
+                        .to_string()
+                        .contains("Publisher on robot1/goal_pose expects PoseStamped, got Twist"),
+                    "{}",
+                    error
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn subscriptions_deliver_decoded_odometry() {
+    let harness = Harness::new().await;
+    let publisher = harness.peer.declare_publisher("robot1/odom").await.unwrap();
+
+    harness
+        .run(
+            |services| services.with_message_services(registry()),
+            |bootstrap| async move {
+                let (_subscription, mut rx) = subscribe_odometry(&bootstrap, 0.0).await;
+                let received = first_delivery(&publisher, &mut rx, &odometry(1)).await;
+                assert_eq!(received, odometry(1));
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn max_rate_drops_messages_arriving_too_fast() {
+    let harness = Harness::new().await;
+    let publisher = harness.peer.declare_publisher("robot1/odom").await.unwrap();
+
+    harness
+        .run(
+            |services| services.with_message_services(registry()),
+            |bootstrap| async move {
+                // At most one message a second
+                let (_subscription, mut rx) = subscribe_odometry(&bootstrap, 1.0).await;
+                first_delivery(&publisher, &mut rx, &odometry(0)).await;
+
+                // A burst well inside the second after the first delivery is dropped
+                for sec in 1..=10 {
+                    put(&publisher, &odometry(sec)).await;
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+                tokio::time::sleep(Duration::from_millis(900)).await;
+                assert!(rx.try_recv().is_err(), "Burst was not downsampled");
+
+                // Once the second has passed the next message goes through
+                put(&publisher, &odometry(11)).await;
+                let received = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(received.header.stamp.sec, 11);
+            },
+        )
+        .await;
+}
@@ -0,0 +1,345 @@
+// Parameters of a ROS node on a peer session, read, written and listed through the router
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use cdr::{CdrLe, Infinite};
+use common::Harness;
+use router::schema_capnp::{bootstrap, parameter_value};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// The services of "/demo/talker" are keyed without the leading slash
+const NODE: &str = "demo/talker";
+
+// rcl_interfaces/msg/ParameterValue as the node sees it on the wire
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Default)]
+struct WireValue {
+    kind: u8,
+    bool_value: bool,
+    integer_value: i64,
+    double_value: f64,
+    string_value: String,
+    byte_array_value: Vec<u8>,
+    bool_array_value: Vec<bool>,
+    integer_array_value: Vec<i64>,
+    double_array_value: Vec<f64>,
+    string_array_value: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct WireParameter {
+    name: String,
+    value: WireValue,
+}
+
+#[derive(Deserialize)]
+struct GetParametersRequest {
+    names: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct GetParametersResponse {
+    values: Vec<WireValue>,
+}
+
+#[derive(Deserialize)]
+struct SetParametersRequest {
+    parameters: Vec<WireParameter>,
+}
+
+#[derive(Serialize)]
+struct SetParametersResult {
+    successful: bool,
+    reason: String,
+}
+
+#[derive(Serialize)]
+struct SetParametersResponse {
+    results: Vec<SetParametersResult>,
+}
+
+#[derive(Deserialize)]
+struct ListParametersRequest {
+    prefixes: Vec<String>,
+    depth: u64,
+}
+
+#[derive(Serialize)]
+struct ListParametersResponse {
+    names: Vec<String>,
+    prefixes: Vec<String>,
+}
+
+// The ParameterValue union as a client sees it
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    NotSet,
+    Bool(bool),
+    Integer(i64),
+    Double(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Bools(Vec<bool>),
+    Integers(Vec<i64>),
+    Doubles(Vec<f64>),
+    Strings(Vec<String>),
+}
+
+impl Value {
+    fn write(&self, mut builder: parameter_value::Builder) {
+        match self {
+            Self::NotSet => builder.set_not_set(()),
+            Self::Bool(v) => builder.set_bool_value(*v),
+            Self::Integer(v) => builder.set_integer_value(*v),
+            Self::Double(v) => builder.set_double_value(*v),
+            Self::String(v) => builder.set_string_value(v),
+            Self::Bytes(v) => builder.set_byte_array_value(v),
+            Self::Bools(v) => {
+                let mut list = builder.init_bool_array_value(v.len() as u32);
+                for (i, v) in v.iter().enumerate() {
+                    list.set(i as u32, *v);
+                }
+            }
+            Self::Integers(v) => {
+                let mut list = builder.init_integer_array_value(v.len() as u32);
+                for (i, v) in v.iter().enumerate() {
+                    list.set(i as u32, *v);
+                }
+            }
+            Self::Doubles(v) => {
+                let mut list = builder.init_double_array_value(v.len() as u32);
+                for (i, v) in v.iter().enumerate() {
+                    list.set(i as u32, *v);
+                }
+            }
+            Self::Strings(v) => {
+                let mut list = builder.init_string_array_value(v.len() as u32);
+                for (i, v) in v.iter().enumerate() {
+                    list.set(i as u32, v);
+                }
+            }
+        }
+    }
+
+    fn read(reader: parameter_value::Reader) -> Self {
+        use parameter_value::Which;
+
+        match reader.which().unwrap() {
+            Which::NotSet(()) => Self::NotSet,
+            Which::BoolValue(v) => Self::Bool(v),
+            Which::IntegerValue(v) => Self::Integer(v),
+            Which::DoubleValue(v) => Self::Double(v),
+            Which::StringValue(v) => Self::String(v.unwrap().to_string().unwrap()),
+            Which::ByteArrayValue(v) => Self::Bytes(v.unwrap().to_vec()),
+            Which::BoolArrayValue(v) => Self::Bools(v.unwrap().iter().collect()),
+            Which::IntegerArrayValue(v) => Self::Integers(v.unwrap().iter().collect()),
+            Which::DoubleArrayValue(v) => Self::Doubles(v.unwrap().iter().collect()),
+            Which::StringArrayValue(v) => Self::Strings(
+                v.unwrap()
+                    .iter()
+                    .map(|s| s.unwrap().to_string().unwrap())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn parameters() -> Vec<(&'static str, Value)> {
+    vec![
+        ("use_sim_time", Value::Bool(true)),
+        ("rate", Value::Integer(-42)),
+        ("gain", Value::Double(0.25)),
+        ("frame", Value::String("base_link".to_string())),
+        ("key", Value::Bytes(vec![0, 1, 254])),
+        ("flags", Value::Bools(vec![true, false, true])),
+        ("ids", Value::Integers(vec![1, -2, i64::MAX])),
+        ("weights", Value::Doubles(vec![0.5, -1.5])),
+        (
+            "names",
+            Value::Strings(vec!["a".to_string(), "".to_string()]),
+        ),
+    ]
+}
+
+fn decode<T: DeserializeOwned>(query: &zenoh::query::Query) -> T {
+    cdr::deserialize(&query.payload().unwrap().to_bytes()).unwrap()
+}
+
+async fn reply<T: Serialize>(query: &zenoh::query::Query, response: &T) {
+    query
+        .reply(
+            query.key_expr().clone(),
+            cdr::serialize::<_, _, CdrLe>(response, Infinite).unwrap(),
+        )
+        .await
+        .unwrap();
+}
+
+// Keeps the parameters of NODE in `store`, unknown names read as not set
+async fn serve_parameters(
+    session: &zenoh::Session,
+    store: Arc<Mutex<BTreeMap<String, WireValue>>>,
+) -> Vec<tokio::task::JoinHandle<()>> {
+    let get = session
+        .declare_queryable(format!("{}/get_parameters", NODE))
+        .await
+        .unwrap();
+    let set = session
+        .declare_queryable(format!("{}/set_parameters", NODE))
+        .await
+        .unwrap();
+    let list = session
+        .declare_queryable(format!("{}/list_parameters", NODE))
+        .await
+        .unwrap();
+
+    let get_store = store.clone();
+    let gets = tokio::spawn(async move {
+        while let Ok(query) = get.recv_async().await {
+            let request: GetParametersRequest = decode(&query);
+            let values = {
+                let store = get_store.lock().unwrap();
+                request
+                    .names
+                    .iter()
+                    .map(|name| store.get(name).cloned().unwrap_or_default())
+                    .collect()
+            };
+            reply(&query, &GetParametersResponse { values }).await;
+        }
+    });
+    let set_store = store.clone();
+    let sets = tokio::spawn(async move {
+        while let Ok(query) = set.recv_async().await {
+            let request: SetParametersRequest = decode(&query);
+            let results = request
+                .parameters
+                .into_iter()
+                .map(|parameter| {
+                    set_store
+                        .lock()
+                        .unwrap()
+                        .insert(parameter.name, parameter.value);
+                    SetParametersResult {
+                        successful: true,
+                        reason: String::new(),
+                    }
+                })
+                .collect();
+            reply(&query, &SetParametersResponse { results }).await;
+        }
+    });
+    let lists = tokio::spawn(async move {
+        while let Ok(query) = list.recv_async().await {
+            let request: ListParametersRequest = decode(&query);
+            assert!(request.prefixes.is_empty());
+            assert_eq!(request.depth, 0);
+            let names = store.lock().unwrap().keys().cloned().collect();
+            let response = ListParametersResponse {
+                names,
+                prefixes: Vec::new(),
+            };
+            reply(&query, &response).await;
+        }
+    });
+    vec![gets, sets, lists]
+}
+
+// The node's queryables take a moment to reach the router
+async fn set_parameters(bootstrap: &bootstrap::Client, node: &str) -> Vec<bool> {
+    for _ in 0..50 {
+        let mut request = bootstrap.set_parameters_request();
+        let mut params = request.get();
+        params.set_node(node);
+        let parameters = parameters();
+        let mut list = params.init_parameters(parameters.len() as u32);
+        for (i, (name, value)) in parameters.iter().enumerate() {
+            let mut parameter = list.reborrow().get(i as u32);
+            parameter.set_name(name);
+            value.write(parameter.init_value());
+        }
+        if let Ok(response) = request.send().promise.await {
+            return response
+                .get()
+                .unwrap()
+                .get_results()
+                .unwrap()
+                .iter()
+                .map(|result| result.get_successful())
+                .collect();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Parameters of {} were never set", node);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn parameter_values_round_trip_through_the_node() {
+    let harness = Harness::new().await;
+    let store = Arc::new(Mutex::new(BTreeMap::new()));
+    let _node = serve_parameters(&harness.peer, store.clone()).await;
+
+    harness
+        .run(
+            |services| services.with_parameter_services(),
+            |bootstrap| async move {
+                let results = set_parameters(&bootstrap, &format!("/{}", NODE)).await;
+                assert_eq!(results, vec![true; parameters().len()]);
+
+                // The node received rcl_interfaces values tagged with their ParameterType
+                {
+                    let store = store.lock().unwrap();
+                    assert_eq!(store["rate"].kind, 2);
+                    assert_eq!(store["rate"].integer_value, -42);
+                    assert_eq!(store["weights"].kind, 8);
+                    assert_eq!(store["weights"].double_array_value, vec![0.5, -1.5]);
+                }
+
+                let mut request = bootstrap.get_parameters_request();
+                let mut params = request.get();
+                params.set_node(format!("/{}", NODE));
+                let mut names: Vec<&str> = parameters().iter().map(|(name, _)| *name).collect();
+                names.push("missing");
+                let mut list = params.init_names(names.len() as u32);
+                for (i, name) in names.iter().enumerate() {
+                    list.set(i as u32, name);
+                }
+                let response = request.send().promise.await.unwrap();
+                let values: Vec<Value> = response
+                    .get()
+                    .unwrap()
+                    .get_values()
+                    .unwrap()
+                    .iter()
+                    .map(Value::read)
+                    .collect();
+                let mut expected: Vec<Value> =
+                    parameters().into_iter().map(|(_, value)| value).collect();
+                expected.push(Value::NotSet);
+                assert_eq!(values, expected);
+
+                let mut request = bootstrap.list_parameters_request();
+                request.get().set_node(format!("/{}", NODE));
+                let response = request.send().promise.await.unwrap();
+                let listed: Vec<String> = response
+                    .get()
+                    .unwrap()
+                    .get_names()
+                    .unwrap()
+                    .iter()
+                    .map(|name| name.unwrap().to_string().unwrap())
+                    .collect();
+                let mut expected: Vec<String> = parameters()
+                    .iter()
+                    .map(|(name, _)| name.to_string())
+                    .collect();
+                expected.sort();
+                assert_eq!(listed, expected);
+            },
+        )
+        .await;
+}
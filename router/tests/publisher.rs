@@ -0,0 +1,142 @@
+// Messages of any registry type published through Bootstrap.getPublisher reach peers as CDR
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{Harness, expect_sample};
+use router::TypeRegistry;
+use router::schema_capnp::{bootstrap, generic_publisher};
+
+fn registry() -> Arc<TypeRegistry> {
+    Arc::new(TypeRegistry::load_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../msgs")).unwrap())
+}
+
+fn pose_cdr(fields: [f32; 5]) -> Vec<u8> {
+    // Little endian CDR encapsulation header, then x, y, theta and the two velocities
+    let mut expected = vec![0x00, 0x01, 0x00, 0x00];
+    for value in fields {
+        expected.extend_from_slice(&value.to_le_bytes());
+    }
+    expected
+}
+
+async fn get_publisher(
+    bootstrap: &bootstrap::Client,
+    ros_type: &str,
+) -> Result<generic_publisher::Client, capnp::Error> {
+    let mut request = bootstrap.get_publisher_request();
+    request.get().set_topic("turtle1/pose");
+    request.get().set_ros_type(ros_type);
+    request.send().promise.await?.get()?.get_publisher()
+}
+
+async fn publish_json(
+    publisher: &generic_publisher::Client,
+    json: &str,
+) -> Result<(), capnp::Error> {
+    let mut request = publisher.publish_json_request();
+    request.get().set_json(json);
+    request.send().promise.await.map(|_| ())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn values_and_json_are_published_as_cdr() {
+    let harness = Harness::new().await;
+    let subscriber = harness
+        .peer
+        .declare_subscriber("turtle1/pose")
+        .await
+        .unwrap();
+
+    harness
+        .run(
+            |services| services.with_generic_publishers(registry()),
+            |bootstrap| async move {
+                let publisher = get_publisher(&bootstrap, "turtlesim/Pose").await.unwrap();
+
+                let fields = [1.0, 2.0, 0.5, 0.25, -0.125];
+                let sample = expect_sample(&subscriber, || {
+                    let mut request = publisher.publish_request();
+                    let names = ["x", "y", "theta", "linear_velocity", "angular_velocity"];
+                    let mut list = request
+                        .get()
+                        .init_message()
+                        .init_message(names.len() as u32);
+                    for (i, (name, value)) in names.iter().zip(fields).enumerate() {
+                        let mut field = list.reborrow().get(i as u32);
+                        field.set_name(name);
+                        field.init_value().set_float32(value);
+                    }
+                    async move {
+                        request.send().promise.await.unwrap();
+                    }
+                })
+                .await;
+                assert_eq!(sample.key_expr().as_str(), "turtle1/pose");
+                assert_eq!(sample.payload().to_bytes().as_ref(), pose_cdr(fields));
+                assert_eq!(
+                    sample.encoding().to_string(),
+                    "application/cdr;turtlesim/msg/Pose"
+                );
+
+                // Fields left out of the JSON are zero
+                publish_json(&publisher, r#"{"x": 3.0, "theta": -1.5}"#)
+                    .await
+                    .unwrap();
+                let expected = pose_cdr([3.0, 0.0, -1.5, 0.0, 0.0]);
+                // Earlier attempts of the first publish may still be in flight
+                let sample = loop {
+                    let sample =
+                        tokio::time::timeout(Duration::from_secs(5), subscriber.recv_async())
+                            .await
+                            .expect("No JSON sample")
+                            .unwrap();
+                    if sample.payload().to_bytes().as_ref() != pose_cdr(fields) {
+                        break sample;
+                    }
+                };
+                assert_eq!(sample.payload().to_bytes().as_ref(), expected);
+                assert_eq!(
+                    sample.encoding().to_string(),
+                    "application/cdr;turtlesim/msg/Pose"
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unknown_types_and_mismatched_json_are_rejected() {
+    let harness = Harness::new().await;
+
+    harness
+        .run(
+            |services| services.with_generic_publishers(registry()),
+            |bootstrap| async move {
+                let error = match get_publisher(&bootstrap, "turtlesim/Speed").await {
+                    Ok(_) => panic!("Publisher of an unknown type was created"),
+                    Err(e) => e,
+                };
+                assert!(
+                    error
+                        .to_string()
+                        .contains("Unknown ROS type turtlesim/Speed"),
+                    "{}",
+                    error
+                );
+
+                let publisher = get_publisher(&bootstrap, "turtlesim/Pose").await.unwrap();
+                for json in [r#"{"x": "fast"}"#, r#"{"speed": 1.0}"#] {
+                    let error = publish_json(&publisher, json).await.unwrap_err();
+                    assert!(
+                        error.to_string().contains("Invalid turtlesim/msg/Pose"),
+                        "{}",
+                        error
+                    );
+                }
+            },
+        )
+        .await;
+}
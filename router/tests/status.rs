@@ -0,0 +1,134 @@
+// watchStatus tells clients whether what the router publishes reaches anyone
+
+mod common;
+
+use std::time::Duration;
+
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+use common::Harness;
+use router::schema_capnp::{LinkState, status_listener};
+use tokio::sync::mpsc;
+
+#[derive(Debug)]
+struct Status {
+    state: LinkState,
+    peers: usize,
+    topics: Vec<(String, LinkState)>,
+}
+
+struct Listener(mpsc::UnboundedSender<Status>);
+
+impl status_listener::Server for Listener {
+    fn on_status(
+        &mut self,
+        params: status_listener::OnStatusParams,
+        _: status_listener::OnStatusResults,
+    ) -> Promise<(), capnp::Error> {
+        let status = pry!(pry!(params.get()).get_status());
+        let topics = pry!(status.get_topics())
+            .iter()
+            .map(|topic| {
+                (
+                    topic.get_topic().unwrap().to_string().unwrap(),
+                    topic.get_state().unwrap(),
+                )
+            })
+            .collect();
+        let _ = self.0.send(Status {
+            state: pry!(status.get_state()),
+            peers: pry!(status.get_peers()).len() as usize,
+            topics,
+        });
+        Promise::ok(())
+    }
+}
+
+// Statuses are pushed as they change, skips to the first one in `state`
+async fn expect_state(statuses: &mut mpsc::UnboundedReceiver<Status>, state: LinkState) -> Status {
+    loop {
+        let status = tokio::time::timeout(Duration::from_secs(5), statuses.recv())
+            .await
+            .unwrap_or_else(|_| panic!("No {:?} status", state))
+            .unwrap();
+        if status.state == state {
+            return status;
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn status_follows_subscribers_and_transports() {
+    let harness = Harness::new().await;
+    let peer = harness.peer.clone();
+
+    harness
+        .run(
+            |services| {
+                services
+                    .with_twist_publisher("turtle1/cmd_vel")
+                    .with_status_monitor()
+            },
+            |bootstrap| async move {
+                let (tx, mut statuses) = mpsc::unbounded_channel();
+                let mut request = bootstrap.watch_status_request();
+                request
+                    .get()
+                    .set_listener(capnp_rpc::new_client(Listener(tx)));
+                let _subscription = request.send().promise.await.unwrap();
+
+                // Linked to the peer, which does not subscribe yet
+                let status = expect_state(&mut statuses, LinkState::NoSubscribers).await;
+                assert_eq!(status.peers, 1);
+                assert_eq!(
+                    status.topics,
+                    [("turtle1/cmd_vel".to_string(), LinkState::NoSubscribers)]
+                );
+
+                let subscriber = peer.declare_subscriber("turtle1/cmd_vel").await.unwrap();
+                let status = expect_state(&mut statuses, LinkState::Connected).await;
+                assert_eq!(
+                    status.topics,
+                    [("turtle1/cmd_vel".to_string(), LinkState::Connected)]
+                );
+
+                subscriber.undeclare().await.unwrap();
+                expect_state(&mut statuses, LinkState::NoSubscribers).await;
+
+                // Closing blocks, which is not allowed on the LocalSet
+                tokio::spawn(async move { peer.close().await.unwrap() })
+                    .await
+                    .unwrap();
+                let status = expect_state(&mut statuses, LinkState::Disconnected).await;
+                assert_eq!(status.peers, 0);
+                assert_eq!(
+                    status.topics,
+                    [("turtle1/cmd_vel".to_string(), LinkState::Disconnected)]
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn watch_status_needs_the_monitor() {
+    let harness = Harness::new().await;
+    harness
+        .run(
+            |services| services,
+            |bootstrap| async move {
+                let (tx, _statuses) = mpsc::unbounded_channel();
+                let mut request = bootstrap.watch_status_request();
+                request
+                    .get()
+                    .set_listener(capnp_rpc::new_client(Listener(tx)));
+                let error = request.send().promise.await.err().unwrap();
+                assert!(
+                    error.to_string().contains("Status monitor not configured"),
+                    "{}",
+                    error
+                );
+            },
+        )
+        .await;
+}
@@ -0,0 +1,45 @@
+// Turtle transforms go out through the configured sink like the services' messages
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::Harness;
+use router::ros_types::turtlesim::Pose;
+use router::sink::{Encoding, RecordingSink};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turtle_tf_is_published_through_the_sink() {
+    let harness = Harness::new().await;
+    let recorder = Arc::new(RecordingSink::new());
+    let sink = recorder.clone();
+    let peer = harness.peer.clone();
+
+    harness
+        .run(
+            |services| services.with_sink(sink).with_turtle_tf("world"),
+            |_| async move {
+                let pose = Pose {
+                    x: 2.0,
+                    y: 3.0,
+                    ..Default::default()
+                };
+                let encoded = cdr::serialize::<_, _, cdr::CdrLe>(&pose, cdr::Infinite).unwrap();
+                // The router's pose subscriber takes a moment to reach the peer
+                for _ in 0..50 {
+                    peer.put("turtle1/pose", encoded.clone()).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    let messages = recorder.take();
+                    if let Some(message) = messages.first() {
+                        assert_eq!(message.key, "tf");
+                        assert_eq!(message.encoding, Encoding::cdr("tf2_msgs/msg/TFMessage"));
+                        assert_eq!(message.origin.client_id, "router");
+                        return;
+                    }
+                }
+                panic!("No transform published");
+            },
+        )
+        .await;
+}
@@ -0,0 +1,112 @@
+// Turtlesim services over RPC, answered by the simulator on a peer session
+
+mod common;
+
+use std::time::Duration;
+
+use common::Harness;
+use router::schema_capnp::{pose_service, turtlesim_service};
+use sim::services::CANVAS_KEY;
+use sim::{CANVAS_SIZE, Simulator};
+
+// turtlesim's canvas has 45 pixels per meter with y pointing down
+fn pixel(x: f32, y: f32) -> (u32, u32) {
+    ((x * 45.0) as u32, ((CANVAS_SIZE - y) * 45.0) as u32)
+}
+
+async fn canvas_pixel(session: &zenoh::Session, x: f32, y: f32) -> [u8; 3] {
+    let replies = session.get(CANVAS_KEY).await.unwrap();
+    let reply = replies.recv_async().await.unwrap();
+    let png = reply.result().unwrap().payload().to_bytes();
+    let canvas = image::load_from_memory(&png).unwrap().to_rgb8();
+    let (x, y) = pixel(x, y);
+    canvas.get_pixel(x, y).0
+}
+
+// The simulator's queryables take a moment to reach the router, retries the spawn until it
+// is answered
+async fn spawn(
+    turtlesim: &turtlesim_service::Client,
+    name: &str,
+    x: f32,
+    y: f32,
+) -> pose_service::Client {
+    for _ in 0..50 {
+        let mut request = turtlesim.spawn_request();
+        let mut params = request.get();
+        params.set_name(name);
+        params.set_x(x);
+        params.set_y(y);
+        if let Ok(response) = request.send().promise.await {
+            let results = response.get().unwrap();
+            assert_eq!(results.get_name().unwrap().to_str().unwrap(), name);
+            return results.get_pose().unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Spawning {} was never answered", name);
+}
+
+// Poses arrive every update, polls until one is at `x`, `y`
+async fn expect_pose(pose: &pose_service::Client, x: f32, y: f32) {
+    for _ in 0..50 {
+        if let Ok(response) = pose.get_pose_request().send().promise.await {
+            let latest = response.get().unwrap().get_pose().unwrap();
+            if latest.get_x() == x && latest.get_y() == y {
+                return;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Turtle never reached ({}, {})", x, y);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn turtlesim_services_drive_the_simulator() {
+    let harness = Harness::new().await;
+    let simulator = Simulator::start(&harness.peer).await.unwrap();
+    let session = harness.session.clone();
+
+    harness
+        .run(
+            |services| services.with_turtlesim_service(),
+            |bootstrap| async move {
+                let turtlesim = bootstrap
+                    .get_turtlesim_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+
+                let pose = spawn(&turtlesim, "leo", 1.0, 1.0).await;
+                expect_pose(&pose, 1.0, 1.0).await;
+
+                let mut request = turtlesim.set_pen_request();
+                let mut params = request.get();
+                params.set_name("leo");
+                params.set_r(255);
+                params.set_width(4);
+                request.send().promise.await.unwrap();
+
+                let mut request = turtlesim.teleport_absolute_request();
+                let mut params = request.get();
+                params.set_name("leo");
+                params.set_x(3.0);
+                params.set_y(1.0);
+                request.send().promise.await.unwrap();
+                expect_pose(&pose, 3.0, 1.0).await;
+
+                // The jump is drawn with the red pen, clearing restores the background
+                assert_eq!(canvas_pixel(&session, 2.0, 1.0).await, [255, 0, 0]);
+                turtlesim.clear_request().send().promise.await.unwrap();
+                assert_eq!(canvas_pixel(&session, 2.0, 1.0).await, [69, 86, 255]);
+            },
+        )
+        .await;
+
+    assert_eq!(simulator.pose("leo").unwrap().x, 3.0);
+}
@@ -0,0 +1,221 @@
+// Twists sent over RPC reach Zenoh peers as CDR
+
+mod common;
+
+use std::time::Duration;
+
+use common::{Harness, expect_sample};
+use router::BootstrapServiceBuilder;
+use router::sink::Attachment;
+use zenoh::qos::{CongestionControl, Priority};
+
+fn twist_cdr(linear: [f64; 3], angular: [f64; 3]) -> Vec<u8> {
+    // Little endian CDR encapsulation header, then the six doubles
+    let mut expected = vec![0x00, 0x01, 0x00, 0x00];
+    for value in linear.iter().chain(&angular) {
+        expected.extend_from_slice(&value.to_le_bytes());
+    }
+    expected
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn do_twist_publishes_cdr_on_cmd_vel() {
+    let harness = Harness::new().await;
+    let subscriber = harness
+        .peer
+        .declare_subscriber("turtle1/cmd_vel")
+        .await
+        .unwrap();
+
+    harness
+        .run(
+            |services| services.with_twist_publisher("turtle1/cmd_vel"),
+            |bootstrap| async move {
+                let twist = bootstrap
+                    .get_twist_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                let sample = expect_sample(&subscriber, || {
+                    let mut request = twist.do_twist_request();
+                    let mut data = request.get().init_data();
+                    data.reborrow().init_linear().set_x(1.5);
+                    data.reborrow().init_angular().set_z(-0.5);
+                    async move {
+                        request.send().promise.await.unwrap();
+                    }
+                })
+                .await;
+
+                assert_eq!(sample.key_expr().as_str(), "turtle1/cmd_vel");
+                assert_eq!(
+                    sample.payload().to_bytes().as_ref(),
+                    twist_cdr([1.5, 0.0, 0.0], [0.0, 0.0, -0.5])
+                );
+
+                assert_eq!(
+                    sample.encoding().to_string(),
+                    "application/cdr;geometry_msgs/msg/Twist"
+                );
+
+                // Provenance, stamped with the router's HLC
+                let attachment =
+                    Attachment::decode(&sample.attachment().unwrap().to_bytes()).unwrap();
+                assert!(!attachment.origin.client_id.is_empty());
+                assert_eq!(
+                    attachment.timestamp,
+                    sample.timestamp().unwrap().to_string()
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn service_qos_reaches_peers() {
+    let harness = Harness::new().await;
+    let subscriber = harness
+        .peer
+        .declare_subscriber("turtle1/cmd_vel")
+        .await
+        .unwrap();
+
+    harness
+        .run(
+            |services| {
+                services
+                    .with_twist_publisher("turtle1/cmd_vel")
+                    .with_service_qos("twist", "1:express:block".parse().unwrap())
+            },
+            |bootstrap| async move {
+                let twist = bootstrap
+                    .get_twist_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                let sample = expect_sample(&subscriber, || {
+                    let mut request = twist.do_twist_request();
+                    request.get().init_data().init_linear().set_x(1.0);
+                    async move {
+                        request.send().promise.await.unwrap();
+                    }
+                })
+                .await;
+
+                assert_eq!(sample.priority(), Priority::RealTime);
+                assert!(sample.express());
+                assert_eq!(sample.congestion_control(), CongestionControl::Block);
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unconfigured_services_are_reported() {
+    let harness = Harness::new().await;
+    harness
+        .run(
+            |services| services,
+            |bootstrap| async move {
+                let error = bootstrap
+                    .get_twist_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .err()
+                    .unwrap();
+                assert!(
+                    error.to_string().contains("Twist publisher not configured"),
+                    "{}",
+                    error
+                );
+            },
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn qos_for_unknown_services_is_rejected() {
+    let harness = Harness::new().await;
+    let error = BootstrapServiceBuilder::new(harness.peer.clone())
+        .with_service_qos("turtlesim", "1".parse().unwrap())
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("does not publish"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pipelined_twists_go_out_in_sequence() {
+    let harness = Harness::new().await;
+    let subscriber = harness
+        .peer
+        .declare_subscriber("turtle1/cmd_vel")
+        .await
+        .unwrap();
+
+    harness
+        .run(
+            |services| services.with_twist_publisher("turtle1/cmd_vel"),
+            |bootstrap| async move {
+                let twist = bootstrap
+                    .get_twist_service_request()
+                    .send()
+                    .promise
+                    .await
+                    .unwrap()
+                    .get()
+                    .unwrap()
+                    .get_service()
+                    .unwrap();
+                let sequence = |sample: &zenoh::sample::Sample| {
+                    Attachment::decode(&sample.attachment().unwrap().to_bytes())
+                        .unwrap()
+                        .origin
+                        .sequence
+                };
+                let first = expect_sample(&subscriber, || {
+                    let request = twist.do_twist_request();
+                    async move {
+                        request.send().promise.await.unwrap();
+                    }
+                })
+                .await;
+                // Let the retries of expect_sample arrive before counting
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                let mut last = sequence(&first);
+                while let Ok(Some(sample)) = subscriber.try_recv() {
+                    last = sequence(&sample);
+                }
+
+                // Sent without waiting for each other
+                let calls: Vec<_> = (0..20)
+                    .map(|_| twist.do_twist_request().send().promise)
+                    .collect();
+                for result in futures::future::join_all(calls).await {
+                    result.unwrap();
+                }
+                for _ in 0..20 {
+                    let sample =
+                        tokio::time::timeout(Duration::from_secs(5), subscriber.recv_async())
+                            .await
+                            .unwrap()
+                            .unwrap();
+                    assert_eq!(sequence(&sample), last + 1);
+                    last += 1;
+                }
+            },
+        )
+        .await;
+}
@@ -0,0 +1,109 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, Rgb, RgbImage};
+
+use crate::turtle::CANVAS_SIZE;
+
+// turtlesim's window is 500x500 pixels with 45 pixels per meter
+pub const WIDTH: u32 = 500;
+pub const HEIGHT: u32 = 500;
+const PIXELS_PER_METER: f32 = 45.0;
+
+// Default background color of turtlesim
+const BACKGROUND: Rgb<u8> = Rgb([69, 86, 255]);
+
+// turtlesim/srv/SetPen, off is a bool sent as a byte
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Pen {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub width: u8,
+    pub off: bool,
+}
+
+impl Default for Pen {
+    fn default() -> Self {
+        Self {
+            r: 179,
+            g: 184,
+            b: 255,
+            width: 3,
+            off: false,
+        }
+    }
+}
+
+// The trails drawn by the turtles' pens
+pub struct Canvas {
+    image: RgbImage,
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self {
+            image: RgbImage::from_pixel(WIDTH, HEIGHT, BACKGROUND),
+        }
+    }
+}
+
+// Canvas meters to pixels, y points down in the image
+fn to_pixels(x: f32, y: f32) -> (f32, f32) {
+    (x * PIXELS_PER_METER, (CANVAS_SIZE - y) * PIXELS_PER_METER)
+}
+
+impl Canvas {
+    pub fn clear(&mut self) {
+        self.image = RgbImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+    }
+
+    // Draws a line between two positions in meters, as wide as the pen in pixels
+    pub fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), pen: &Pen) {
+        if pen.off || pen.width == 0 {
+            return;
+        }
+        let (x0, y0) = to_pixels(from.0, from.1);
+        let (x1, y1) = to_pixels(to.0, to.1);
+        let radius = pen.width as f32 / 2.0;
+
+        // Fill every pixel whose center is within the radius of the segment
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let length_squared = dx * dx + dy * dy;
+        let left = (x0.min(x1) - radius).floor().max(0.0) as u32;
+        let right = ((x0.max(x1) + radius).ceil().max(0.0) as u32).min(WIDTH);
+        let top = (y0.min(y1) - radius).floor().max(0.0) as u32;
+        let bottom = ((y0.max(y1) + radius).ceil().max(0.0) as u32).min(HEIGHT);
+        for py in top..bottom {
+            for px in left..right {
+                let (cx, cy) = (px as f32 + 0.5, py as f32 + 0.5);
+                let t = match length_squared {
+                    0.0 => 0.0,
+                    _ => (((cx - x0) * dx + (cy - y0) * dy) / length_squared).clamp(0.0, 1.0),
+                };
+                let (nx, ny) = (x0 + t * dx - cx, y0 + t * dy - cy);
+                if nx * nx + ny * ny <= radius * radius {
+                    self.image.put_pixel(px, py, Rgb([pen.r, pen.g, pen.b]));
+                }
+            }
+        }
+    }
+
+    // Color at a position in meters
+    pub fn color_at(&self, x: f32, y: f32) -> Option<[u8; 3]> {
+        let (px, py) = to_pixels(x, y);
+        if px < 0.0 || py < 0.0 {
+            return None;
+        }
+        self.image
+            .get_pixel_checked(px as u32, py as u32)
+            .map(|pixel| pixel.0)
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut data = Cursor::new(Vec::new());
+        self.image
+            .write_to(&mut data, ImageFormat::Png)
+            .expect("Encoding an RGB image as PNG in memory cannot fail");
+        data.into_inner()
+    }
+}
@@ -0,0 +1,106 @@
+// Kinematic stand-in for turtlesim, speaking the same Zenoh keys and CDR messages as the
+// turtlesim node behind the DDS bridge
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use cdr::{CdrLe, Infinite};
+use tokio::task::JoinHandle;
+use zenoh::bytes::Encoding;
+
+pub mod canvas;
+pub mod services;
+#[cfg(test)]
+mod simulator_tests;
+pub mod turtle;
+#[cfg(test)]
+mod turtle_tests;
+pub mod world;
+#[cfg(test)]
+mod world_tests;
+
+pub use canvas::Pen;
+pub use turtle::{CANVAS_SIZE, Pose, Turtle, Twist, Vector3};
+pub use world::World;
+
+// turtlesim updates its turtles every 16 milliseconds
+pub const UPDATE_PERIOD: Duration = Duration::from_millis(16);
+
+pub struct Simulator {
+    world: Arc<Mutex<World>>,
+    _subscriber: zenoh::pubsub::Subscriber<()>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Simulator {
+    pub async fn start(session: &zenoh::Session) -> Result<Self, zenoh::Error> {
+        let world = Arc::new(Mutex::new(World::new()));
+
+        let commanded = world.clone();
+        let subscriber = session
+            .declare_subscriber("*/cmd_vel")
+            .callback(move |sample| {
+                let Some(name) = sample.key_expr().as_str().strip_suffix("/cmd_vel") else {
+                    return;
+                };
+                match cdr::deserialize::<Twist>(&sample.payload().to_bytes()) {
+                    Ok(twist) => commanded.lock().unwrap().command(name, &twist),
+                    Err(e) => eprintln!("Failed to decode twist for {}: {}", name, e),
+                }
+            })
+            .await?;
+
+        let mut tasks = services::declare(session, world.clone()).await?;
+
+        let session = session.clone();
+        let updated = world.clone();
+        tasks.push(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(UPDATE_PERIOD);
+            loop {
+                interval.tick().await;
+                let (poses, crashed) = updated.lock().unwrap().step(UPDATE_PERIOD.as_secs_f64());
+                for name in crashed {
+                    eprintln!("Oh no! {} hit the wall!", name);
+                }
+                for (name, pose) in poses {
+                    let key = format!("{}/pose", name);
+                    let encoded = cdr::serialize::<_, _, CdrLe>(&pose, Infinite).unwrap();
+                    let put = session
+                        .put(&key, encoded)
+                        .encoding(Encoding::APPLICATION_CDR.with_schema("turtlesim/msg/Pose"));
+                    if let Err(e) = put.await {
+                        eprintln!("Failed to publish {}: {}", key, e);
+                    }
+                }
+            }
+        }));
+
+        Ok(Self {
+            world,
+            _subscriber: subscriber,
+            tasks,
+        })
+    }
+
+    // Same as the spawn service, an empty name picks the next free turtleN
+    pub fn spawn(&self, name: &str, x: f32, y: f32, theta: f32) -> Result<String, String> {
+        self.world.lock().unwrap().spawn(name, x, y, theta)
+    }
+
+    pub fn pose(&self, name: &str) -> Option<Pose> {
+        self.world.lock().unwrap().pose(name)
+    }
+
+    // The pen trails drawn so far, turtles themselves are not drawn
+    pub fn canvas_png(&self) -> Vec<u8> {
+        self.world.lock().unwrap().canvas.to_png()
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
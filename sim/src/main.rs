@@ -0,0 +1,17 @@
+use sim::Simulator;
+use zenoh::{Config as ZenohConfig, try_init_log_from_env};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    try_init_log_from_env();
+
+    let session = zenoh::open(ZenohConfig::default()).await.unwrap();
+    let _simulator = Simulator::start(&session).await.unwrap();
+    println!(
+        "Simulating turtle1 with turtlesim services, canvas PNG on {}",
+        sim::services::CANVAS_KEY
+    );
+
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
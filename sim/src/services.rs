@@ -0,0 +1,197 @@
+use std::sync::{Arc, Mutex};
+
+use cdr::{CdrLe, Infinite};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use zenoh::bytes::Encoding;
+
+use crate::canvas::Pen;
+use crate::world::World;
+
+// Key of the queryable replying with the canvas as a PNG, turtlesim has no equivalent
+pub const CANVAS_KEY: &str = "sim/canvas";
+
+// Request/response types of the turtlesim services, field order matches the .srv files
+#[derive(Deserialize, Serialize, PartialEq, Default, Debug)]
+pub struct Empty {
+    structure_needs_at_least_one_member: u8,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct SpawnRequest {
+    pub x: f32,
+    pub y: f32,
+    pub theta: f32,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct SpawnResponse {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct KillRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct SetPenRequest {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub width: u8,
+    pub off: u8,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct TeleportAbsoluteRequest {
+    pub x: f32,
+    pub y: f32,
+    pub theta: f32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+pub struct TeleportRelativeRequest {
+    pub linear: f32,
+    pub angular: f32,
+}
+
+// Answers queries on `key` until the task is aborted. `handle` gets the turtle name for keys
+// under a turtle, e.g. `turtle1/set_pen` for `*/set_pen`, and an empty name otherwise
+async fn serve<Req, Res, F>(
+    session: &zenoh::Session,
+    key: &'static str,
+    world: Arc<Mutex<World>>,
+    handle: F,
+) -> Result<JoinHandle<()>, zenoh::Error>
+where
+    Req: DeserializeOwned + 'static,
+    Res: Serialize + 'static,
+    F: Fn(&mut World, &str, Req) -> Result<Res, String> + Send + 'static,
+{
+    let queryable = session.declare_queryable(key).await?;
+    let suffix = key.strip_prefix("*/");
+    Ok(tokio::spawn(async move {
+        while let Ok(query) = queryable.recv_async().await {
+            let name = suffix
+                .and_then(|suffix| query.key_expr().as_str().strip_suffix(suffix))
+                .and_then(|prefix| prefix.strip_suffix('/'))
+                .unwrap_or("")
+                .to_string();
+            let payload = query.payload().map(|p| p.to_bytes().into_owned());
+            let response = cdr::deserialize::<Req>(payload.as_deref().unwrap_or_default())
+                .map_err(|e| format!("Failed to decode {} request: {}", key, e))
+                .and_then(|request| handle(&mut world.lock().unwrap(), &name, request))
+                .map(|response| cdr::serialize::<_, _, CdrLe>(&response, Infinite).unwrap());
+            let sent = match response {
+                Ok(encoded) => {
+                    query
+                        .reply(query.key_expr().clone(), encoded)
+                        .encoding(Encoding::APPLICATION_CDR)
+                        .await
+                }
+                Err(e) => {
+                    eprintln!("{}: {}", query.key_expr(), e);
+                    query.reply_err(e).encoding(Encoding::TEXT_PLAIN).await
+                }
+            };
+            if let Err(e) = sent {
+                eprintln!("Failed to reply to {}: {}", query.key_expr(), e);
+            }
+        }
+    }))
+}
+
+// Declares turtlesim's spawn, kill, clear, set_pen and teleport services
+pub async fn declare(
+    session: &zenoh::Session,
+    world: Arc<Mutex<World>>,
+) -> Result<Vec<JoinHandle<()>>, zenoh::Error> {
+    let spawn = serve(
+        session,
+        "spawn",
+        world.clone(),
+        |world, _, request: SpawnRequest| {
+            let name = world.spawn(&request.name, request.x, request.y, request.theta)?;
+            Ok(SpawnResponse { name })
+        },
+    )
+    .await?;
+    let kill = serve(
+        session,
+        "kill",
+        world.clone(),
+        |world, _, request: KillRequest| world.kill(&request.name).map(|_| Empty::default()),
+    )
+    .await?;
+    let clear = serve(session, "clear", world.clone(), |world, _, _: Empty| {
+        world.canvas.clear();
+        Ok(Empty::default())
+    })
+    .await?;
+    let set_pen = serve(
+        session,
+        "*/set_pen",
+        world.clone(),
+        |world, name, request: SetPenRequest| {
+            let pen = Pen {
+                r: request.r,
+                g: request.g,
+                b: request.b,
+                width: request.width,
+                off: request.off != 0,
+            };
+            world.set_pen(name, pen).map(|_| Empty::default())
+        },
+    )
+    .await?;
+    let teleport_absolute = serve(
+        session,
+        "*/teleport_absolute",
+        world.clone(),
+        |world, name, request: TeleportAbsoluteRequest| {
+            world
+                .teleport(name, |turtle| {
+                    turtle.teleport_absolute(request.x, request.y, request.theta)
+                })
+                .map(|_| Empty::default())
+        },
+    )
+    .await?;
+    let teleport_relative = serve(
+        session,
+        "*/teleport_relative",
+        world.clone(),
+        |world, name, request: TeleportRelativeRequest| {
+            world
+                .teleport(name, |turtle| {
+                    turtle.teleport_relative(request.linear, request.angular)
+                })
+                .map(|_| Empty::default())
+        },
+    )
+    .await?;
+
+    let canvas = session.declare_queryable(CANVAS_KEY).await?;
+    let canvas = tokio::spawn(async move {
+        while let Ok(query) = canvas.recv_async().await {
+            let png = world.lock().unwrap().canvas.to_png();
+            let reply = query.reply(CANVAS_KEY, png).encoding(Encoding::IMAGE_PNG);
+            if let Err(e) = reply.await {
+                eprintln!("Failed to reply to {}: {}", CANVAS_KEY, e);
+            }
+        }
+    });
+
+    Ok(vec![
+        spawn,
+        kill,
+        clear,
+        set_pen,
+        teleport_absolute,
+        teleport_relative,
+        canvas,
+    ])
+}
@@ -0,0 +1,141 @@
+// Twists and poses over an in-process Zenoh session
+
+use std::time::Duration;
+
+use cdr::{CdrLe, Infinite};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::services::{
+    CANVAS_KEY, Empty, KillRequest, SetPenRequest, SpawnRequest, SpawnResponse,
+    TeleportAbsoluteRequest,
+};
+use crate::{CANVAS_SIZE, Pose, Simulator, Twist, Vector3};
+
+async fn isolated_session() -> zenoh::Session {
+    let mut config = zenoh::Config::default();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    zenoh::open(config).await.unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn twists_move_turtles_and_poses_are_published() {
+    let session = isolated_session().await;
+    let simulator = Simulator::start(&session).await.unwrap();
+    let poses = session.declare_subscriber("turtle1/pose").await.unwrap();
+
+    let twist = Twist {
+        linear: Vector3 {
+            x: 1.0,
+            ..Default::default()
+        },
+        angular: Vector3::default(),
+    };
+    session
+        .put(
+            "turtle1/cmd_vel",
+            cdr::serialize::<_, _, CdrLe>(&twist, Infinite).unwrap(),
+        )
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Poses queue up every update, the latest one reflects the command
+    let mut sample = poses.recv_async().await.unwrap();
+    while let Ok(Some(next)) = poses.try_recv() {
+        sample = next;
+    }
+    let pose: Pose = cdr::deserialize(&sample.payload().to_bytes()).unwrap();
+    assert_eq!(pose.y, CANVAS_SIZE / 2.0);
+    assert!(pose.x > CANVAS_SIZE / 2.0, "{:?}", pose);
+    assert!(simulator.pose("turtle1").unwrap().x > CANVAS_SIZE / 2.0);
+
+    assert!(simulator.pose("turtle2").is_none());
+}
+
+async fn call<Req: Serialize, Res: DeserializeOwned>(
+    session: &zenoh::Session,
+    key: &str,
+    request: &Req,
+) -> Result<Res, String> {
+    let replies = session
+        .get(key)
+        .payload(cdr::serialize::<_, _, CdrLe>(request, Infinite).unwrap())
+        .await
+        .unwrap();
+    match replies.recv_async().await.unwrap().result() {
+        Ok(sample) => Ok(cdr::deserialize(&sample.payload().to_bytes()).unwrap()),
+        Err(err) => Err(err.payload().try_to_string().unwrap().into_owned()),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn services_answer_like_turtlesim() {
+    let session = isolated_session().await;
+    let simulator = Simulator::start(&session).await.unwrap();
+
+    let spawned: SpawnResponse = call(
+        &session,
+        "spawn",
+        &SpawnRequest {
+            x: 1.0,
+            y: 1.0,
+            theta: 0.0,
+            name: String::new(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(spawned.name, "turtle2");
+
+    let pen = SetPenRequest {
+        r: 255,
+        g: 0,
+        b: 0,
+        width: 4,
+        off: 0,
+    };
+    let _: Empty = call(&session, "turtle2/set_pen", &pen).await.unwrap();
+    let teleport = TeleportAbsoluteRequest {
+        x: 3.0,
+        y: 1.0,
+        theta: 0.0,
+    };
+    let _: Empty = call(&session, "turtle2/teleport_absolute", &teleport)
+        .await
+        .unwrap();
+    assert_eq!(simulator.pose("turtle2").unwrap().x, 3.0);
+
+    // The trail shows up in the rendered canvas, 45 pixels per meter with y pointing down
+    let replies = session.get(CANVAS_KEY).await.unwrap();
+    let sample = replies
+        .recv_async()
+        .await
+        .unwrap()
+        .result()
+        .unwrap()
+        .clone();
+    let canvas = image::load_from_memory(&sample.payload().to_bytes())
+        .unwrap()
+        .to_rgb8();
+    let y = ((CANVAS_SIZE - 1.0) * 45.0) as u32;
+    assert_eq!(canvas.get_pixel(90, y).0, [255, 0, 0]);
+
+    let error = call::<_, Empty>(
+        &session,
+        "kill",
+        &KillRequest {
+            name: "turtle9".to_string(),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        error,
+        "Tried to kill turtle [turtle9], which does not exist"
+    );
+}
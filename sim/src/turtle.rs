@@ -0,0 +1,113 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+// Size of the turtlesim canvas in meters, a 500 pixel window at 45 pixels per meter
+pub const CANVAS_SIZE: f32 = 499.0 / 45.0;
+
+// Turtles stop when no command arrives for this long
+const COMMAND_TIMEOUT: f64 = 1.0;
+
+// geometry_msgs/Vector3, field order matches the .msg file
+#[derive(Deserialize, Serialize, PartialEq, Clone, Copy, Debug, Default)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+// geometry_msgs/Twist
+#[derive(Deserialize, Serialize, PartialEq, Clone, Copy, Debug, Default)]
+pub struct Twist {
+    pub linear: Vector3,
+    pub angular: Vector3,
+}
+
+// turtlesim/Pose
+#[derive(Deserialize, Serialize, PartialEq, Clone, Copy, Debug, Default)]
+pub struct Pose {
+    pub x: f32,
+    pub y: f32,
+    pub theta: f32,
+    pub linear_velocity: f32,
+    pub angular_velocity: f32,
+}
+
+// Kinematics of a single turtle, following turtlesim's Turtle::update
+#[derive(Clone, Debug)]
+pub struct Turtle {
+    x: f32,
+    y: f32,
+    theta: f32,
+    linear_x: f32,
+    linear_y: f32,
+    angular: f32,
+    // Simulated seconds since the last command
+    since_command: f64,
+}
+
+impl Turtle {
+    pub fn new(x: f32, y: f32, theta: f32) -> Self {
+        Self {
+            x,
+            y,
+            theta,
+            linear_x: 0.0,
+            linear_y: 0.0,
+            angular: 0.0,
+            since_command: 0.0,
+        }
+    }
+
+    pub fn command(&mut self, twist: &Twist) {
+        self.linear_x = twist.linear.x as f32;
+        self.linear_y = twist.linear.y as f32;
+        self.angular = twist.angular.z as f32;
+        self.since_command = 0.0;
+    }
+
+    // Advances the turtle by `dt` seconds, returns false when it hit a wall
+    pub fn update(&mut self, dt: f64) -> bool {
+        self.since_command += dt;
+        if self.since_command > COMMAND_TIMEOUT {
+            self.linear_x = 0.0;
+            self.linear_y = 0.0;
+            self.angular = 0.0;
+        }
+
+        let dt = dt as f32;
+        self.theta = (self.theta + self.angular * dt) % (2.0 * PI);
+        // The body frame velocity rotated into the world frame, y points to the turtle's left
+        let x =
+            self.x + self.theta.cos() * self.linear_x * dt - self.theta.sin() * self.linear_y * dt;
+        let y =
+            self.y + self.theta.sin() * self.linear_x * dt + self.theta.cos() * self.linear_y * dt;
+
+        self.x = x.clamp(0.0, CANVAS_SIZE);
+        self.y = y.clamp(0.0, CANVAS_SIZE);
+        self.x == x && self.y == y
+    }
+
+    pub fn teleport_absolute(&mut self, x: f32, y: f32, theta: f32) {
+        self.x = x;
+        self.y = y;
+        self.theta = theta;
+    }
+
+    // Turns first, then moves along the new heading
+    pub fn teleport_relative(&mut self, linear: f32, angular: f32) {
+        self.theta = (self.theta + angular) % (2.0 * PI);
+        self.x += self.theta.cos() * linear;
+        self.y += self.theta.sin() * linear;
+    }
+
+    pub fn pose(&self) -> Pose {
+        Pose {
+            x: self.x,
+            y: self.y,
+            theta: self.theta,
+            linear_velocity: self.linear_x.hypot(self.linear_y),
+            angular_velocity: self.angular,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Turtlesim kinematics, command timeout and wall clamping
+
+use std::f32::consts::FRAC_PI_2;
+
+use crate::turtle::{CANVAS_SIZE, Turtle, Twist, Vector3};
+
+const DT: f64 = 0.016;
+
+fn twist(linear: f64, angular: f64) -> Twist {
+    Twist {
+        linear: Vector3 {
+            x: linear,
+            ..Default::default()
+        },
+        angular: Vector3 {
+            z: angular,
+            ..Default::default()
+        },
+    }
+}
+
+fn run(turtle: &mut Turtle, seconds: f64) -> bool {
+    let mut clear = true;
+    for _ in 0..(seconds / DT).round() as usize {
+        clear &= turtle.update(DT);
+    }
+    clear
+}
+
+#[test]
+fn drives_forward_along_heading() {
+    let mut turtle = Turtle::new(1.0, 1.0, FRAC_PI_2);
+    turtle.command(&twist(2.0, 0.0));
+    assert!(run(&mut turtle, 0.48));
+
+    let pose = turtle.pose();
+    assert!((pose.x - 1.0).abs() < 1e-4, "{:?}", pose);
+    assert!((pose.y - 1.96).abs() < 1e-4, "{:?}", pose);
+    assert_eq!(pose.linear_velocity, 2.0);
+}
+
+#[test]
+fn strafes_to_the_left_of_heading() {
+    // Facing north, the turtle's left is west
+    let mut turtle = Turtle::new(5.0, 5.0, FRAC_PI_2);
+    let mut command = twist(0.0, 0.0);
+    command.linear.y = 1.0;
+    turtle.command(&command);
+    assert!(run(&mut turtle, 0.48));
+
+    let pose = turtle.pose();
+    assert!((pose.x - 4.52).abs() < 1e-4, "{:?}", pose);
+    assert!((pose.y - 5.0).abs() < 1e-4, "{:?}", pose);
+    assert_eq!(pose.linear_velocity, 1.0);
+}
+
+#[test]
+fn turns_in_place() {
+    let mut turtle = Turtle::new(5.0, 5.0, 0.0);
+    turtle.command(&twist(0.0, 1.0));
+    run(&mut turtle, 0.8);
+
+    let pose = turtle.pose();
+    assert!((pose.theta - 0.8).abs() < 1e-4, "{:?}", pose);
+    assert_eq!((pose.x, pose.y), (5.0, 5.0));
+    assert_eq!(pose.angular_velocity, 1.0);
+}
+
+#[test]
+fn stops_without_commands() {
+    let mut turtle = Turtle::new(1.0, 5.0, 0.0);
+    turtle.command(&twist(1.0, 0.0));
+    run(&mut turtle, 3.0);
+
+    // Only the first second of motion counts
+    let pose = turtle.pose();
+    assert!((pose.x - 2.0).abs() < 0.02, "{:?}", pose);
+    assert_eq!(pose.linear_velocity, 0.0);
+}
+
+#[test]
+fn clamps_at_walls() {
+    let mut turtle = Turtle::new(CANVAS_SIZE - 0.1, 0.1, 0.0);
+    turtle.command(&twist(1.0, 0.0));
+    assert!(!run(&mut turtle, 0.5));
+    assert_eq!(turtle.pose().x, CANVAS_SIZE);
+
+    turtle.command(&twist(-20.0, 0.0));
+    assert!(!run(&mut turtle, 0.9));
+    assert_eq!(turtle.pose().x, 0.0);
+}
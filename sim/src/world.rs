@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use crate::canvas::{Canvas, Pen};
+use crate::turtle::{CANVAS_SIZE, Pose, Turtle, Twist};
+
+struct SimTurtle {
+    turtle: Turtle,
+    pen: Pen,
+}
+
+// Turtles and the canvas they draw on, errors are the messages turtlesim replies with
+#[derive(Default)]
+pub struct World {
+    turtles: BTreeMap<String, SimTurtle>,
+    pub canvas: Canvas,
+    // Turtles spawned without a name are called turtle1, turtle2, ...
+    spawned: u32,
+}
+
+impl World {
+    // Starts with turtle1 in the middle of the canvas, like turtlesim_node
+    pub fn new() -> Self {
+        let mut world = Self::default();
+        world
+            .spawn("", CANVAS_SIZE / 2.0, CANVAS_SIZE / 2.0, 0.0)
+            .unwrap();
+        world
+    }
+
+    pub fn spawn(&mut self, name: &str, x: f32, y: f32, theta: f32) -> Result<String, String> {
+        let name = match name {
+            "" => loop {
+                self.spawned += 1;
+                let name = format!("turtle{}", self.spawned);
+                if !self.turtles.contains_key(&name) {
+                    break name;
+                }
+            },
+            name if self.turtles.contains_key(name) => {
+                return Err(format!("A turtle named [{}] already exists", name));
+            }
+            name => name.to_string(),
+        };
+        self.turtles.insert(
+            name.clone(),
+            SimTurtle {
+                turtle: Turtle::new(x, y, theta),
+                pen: Pen::default(),
+            },
+        );
+        Ok(name)
+    }
+
+    pub fn kill(&mut self, name: &str) -> Result<(), String> {
+        self.turtles
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| format!("Tried to kill turtle [{}], which does not exist", name))
+    }
+
+    fn get_mut(&mut self, name: &str) -> Result<&mut SimTurtle, String> {
+        self.turtles
+            .get_mut(name)
+            .ok_or_else(|| format!("Turtle [{}] does not exist", name))
+    }
+
+    // Commands for turtles that do not exist are ignored, as by turtlesim
+    pub fn command(&mut self, name: &str, twist: &Twist) {
+        if let Some(sim) = self.turtles.get_mut(name) {
+            sim.turtle.command(twist);
+        }
+    }
+
+    pub fn set_pen(&mut self, name: &str, pen: Pen) -> Result<(), String> {
+        self.get_mut(name)?.pen = pen;
+        Ok(())
+    }
+
+    // Moves a turtle with `teleport`, drawing the jump when the pen is down
+    pub fn teleport(
+        &mut self,
+        name: &str,
+        teleport: impl FnOnce(&mut Turtle),
+    ) -> Result<(), String> {
+        let sim = self
+            .turtles
+            .get_mut(name)
+            .ok_or_else(|| format!("Turtle [{}] does not exist", name))?;
+        let before = sim.turtle.pose();
+        teleport(&mut sim.turtle);
+        let after = sim.turtle.pose();
+        self.canvas
+            .draw_line((before.x, before.y), (after.x, after.y), &sim.pen);
+        Ok(())
+    }
+
+    // Advances every turtle by `dt` seconds, returns their poses and the turtles that hit a wall
+    pub fn step(&mut self, dt: f64) -> (Vec<(String, Pose)>, Vec<String>) {
+        let mut poses = Vec::with_capacity(self.turtles.len());
+        let mut crashed = Vec::new();
+        for (name, sim) in &mut self.turtles {
+            let before = sim.turtle.pose();
+            if !sim.turtle.update(dt) {
+                crashed.push(name.clone());
+            }
+            let after = sim.turtle.pose();
+            if (before.x, before.y) != (after.x, after.y) {
+                self.canvas
+                    .draw_line((before.x, before.y), (after.x, after.y), &sim.pen);
+            }
+            poses.push((name.clone(), after));
+        }
+        (poses, crashed)
+    }
+
+    pub fn pose(&self, name: &str) -> Option<Pose> {
+        self.turtles.get(name).map(|sim| sim.turtle.pose())
+    }
+}
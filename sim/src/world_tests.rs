@@ -0,0 +1,89 @@
+// Spawning, killing and the trails drawn by the pens
+
+use crate::canvas::Pen;
+use crate::turtle::{CANVAS_SIZE, Twist, Vector3};
+use crate::world::World;
+
+const BACKGROUND: [u8; 3] = [69, 86, 255];
+const DEFAULT_PEN: [u8; 3] = [179, 184, 255];
+
+#[test]
+fn names_spawned_turtles_like_turtlesim() {
+    let mut world = World::new();
+    assert!(world.pose("turtle1").is_some());
+    assert_eq!(world.spawn("", 1.0, 1.0, 0.0).unwrap(), "turtle2");
+    assert_eq!(world.spawn("turtle3", 1.0, 1.0, 0.0).unwrap(), "turtle3");
+    // Taken names are skipped
+    assert_eq!(world.spawn("", 1.0, 1.0, 0.0).unwrap(), "turtle4");
+    assert_eq!(
+        world.spawn("turtle1", 1.0, 1.0, 0.0).unwrap_err(),
+        "A turtle named [turtle1] already exists"
+    );
+
+    world.kill("turtle2").unwrap();
+    assert!(world.pose("turtle2").is_none());
+    assert_eq!(
+        world.kill("turtle2").unwrap_err(),
+        "Tried to kill turtle [turtle2], which does not exist"
+    );
+}
+
+#[test]
+fn pens_draw_trails_until_cleared() {
+    let mut world = World::new();
+    let center = CANVAS_SIZE / 2.0;
+    world.command(
+        "turtle1",
+        &Twist {
+            linear: Vector3 {
+                x: 2.0,
+                ..Default::default()
+            },
+            angular: Vector3::default(),
+        },
+    );
+    for _ in 0..30 {
+        world.step(0.016);
+    }
+    assert_eq!(
+        world.canvas.color_at(center + 0.5, center),
+        Some(DEFAULT_PEN)
+    );
+    assert_eq!(
+        world.canvas.color_at(center + 0.5, center + 0.5),
+        Some(BACKGROUND)
+    );
+
+    world.canvas.clear();
+    assert_eq!(
+        world.canvas.color_at(center + 0.5, center),
+        Some(BACKGROUND)
+    );
+}
+
+#[test]
+fn teleports_draw_unless_the_pen_is_off() {
+    let mut world = World::new();
+    let red = Pen {
+        r: 255,
+        g: 0,
+        b: 0,
+        width: 5,
+        off: false,
+    };
+    world.set_pen("turtle1", red).unwrap();
+    world
+        .teleport("turtle1", |turtle| turtle.teleport_absolute(1.0, 1.0, 0.0))
+        .unwrap();
+    assert_eq!(world.canvas.color_at(2.0, 2.0), Some([255, 0, 0]));
+
+    world.set_pen("turtle1", Pen { off: true, ..red }).unwrap();
+    world
+        .teleport("turtle1", |turtle| turtle.teleport_relative(2.0, 0.0))
+        .unwrap();
+    let pose = world.pose("turtle1").unwrap();
+    assert_eq!((pose.x, pose.y), (3.0, 1.0));
+    assert_eq!(world.canvas.color_at(2.0, 1.0), Some(BACKGROUND));
+
+    assert!(world.set_pen("turtle9", red).is_err());
+}